@@ -0,0 +1,208 @@
+use axum::http::HeaderValue;
+use clap::Parser;
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use tower_http::cors::{AllowOrigin, Any};
+
+/// Config file that is read when no `--config` / `FRACTAL_CONFIG` is given and it exists.
+pub const DEFAULT_CONFIG_PATH: &str = "fractal.toml";
+
+/// CORS origin that allows requests from any origin.
+pub const ANY_ORIGIN: &str = "*";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error(transparent)]
+    Cli(#[from] clap::Error),
+    #[error("Failed to read config file '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse config file '{path}': {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("Invalid configuration: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+}
+
+/// Server configuration.
+///
+/// Values are layered, each source overriding the previous one: built-in defaults, the TOML
+/// config file, `FRACTAL_*` environment variables and finally command-line flags.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub bind_address: String,
+    pub db_path: String,
+    pub cors_origins: Vec<String>,
+    pub seed_example_graph: bool,
+    pub log_level: String,
+    pub graphiql: bool,
+    /// Days deleted items stay in the trash before being purged at startup, `0` keeps them.
+    pub trash_retention_days: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bind_address: "127.0.0.1:8000".to_string(),
+            db_path: "./demo_db".to_string(),
+            cors_origins: vec![ANY_ORIGIN.to_string()],
+            seed_example_graph: true,
+            log_level: "debug".to_string(),
+            graphiql: true,
+            trash_retention_days: 30,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Fractal GraphQL server")]
+struct Cli {
+    /// Path to a TOML config file
+    #[arg(long, env = "FRACTAL_CONFIG")]
+    config: Option<PathBuf>,
+    /// Address to listen on, e.g. 127.0.0.1:8000
+    #[arg(long, env = "FRACTAL_BIND_ADDRESS")]
+    bind_address: Option<String>,
+    /// Database directory, or ":memory:" for an in-memory database
+    #[arg(long, env = "FRACTAL_DB_PATH")]
+    db_path: Option<String>,
+    /// Comma-separated list of allowed CORS origins, or "*" for any origin
+    #[arg(long, env = "FRACTAL_CORS_ORIGINS", value_delimiter = ',')]
+    cors_origins: Option<Vec<String>>,
+    /// Seed the example graph when creating a new database
+    #[arg(long, env = "FRACTAL_SEED_EXAMPLE_GRAPH")]
+    seed_example_graph: Option<bool>,
+    /// Log filter directive, e.g. "info" or "server=debug"
+    #[arg(long, env = "FRACTAL_LOG_LEVEL")]
+    log_level: Option<String>,
+    /// Serve the GraphiQL IDE on GET /
+    #[arg(long, env = "FRACTAL_GRAPHIQL")]
+    graphiql: Option<bool>,
+    /// Days deleted items stay in the trash, 0 keeps them forever
+    #[arg(long, env = "FRACTAL_TRASH_RETENTION_DAYS")]
+    trash_retention_days: Option<u32>,
+}
+
+impl Config {
+    /// Loads and validates the configuration from the process arguments and environment.
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::load_from(std::env::args_os())
+    }
+
+    /// Same as [`Config::load`] but with explicit command-line arguments, the first of which is
+    /// the binary name.
+    pub fn load_from<I, T>(args: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let cli = Cli::try_parse_from(args)?;
+
+        let mut config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).is_file() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_PATH))?
+            }
+            None => Config::default(),
+        };
+
+        if let Some(bind_address) = cli.bind_address {
+            config.bind_address = bind_address;
+        }
+        if let Some(db_path) = cli.db_path {
+            config.db_path = db_path;
+        }
+        if let Some(cors_origins) = cli.cors_origins {
+            config.cors_origins = cors_origins;
+        }
+        if let Some(seed_example_graph) = cli.seed_example_graph {
+            config.seed_example_graph = seed_example_graph;
+        }
+        if let Some(log_level) = cli.log_level {
+            config.log_level = log_level;
+        }
+        if let Some(graphiql) = cli.graphiql {
+            config.graphiql = graphiql;
+        }
+        if let Some(trash_retention_days) = cli.trash_retention_days {
+            config.trash_retention_days = trash_retention_days;
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&content).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Checks every setting and reports all problems at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        if let Err(e) = self.bind_address.parse::<SocketAddr>() {
+            errors.push(format!("bind_address '{}': {}", self.bind_address, e));
+        }
+
+        if self.db_path.trim().is_empty() {
+            errors.push("db_path must not be empty".to_string());
+        }
+
+        if let Err(e) = self.allow_origin() {
+            errors.push(e);
+        }
+
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&self.log_level) {
+            errors.push(format!("log_level '{}': {}", self.log_level, e));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+
+    pub fn socket_addr(&self) -> Result<SocketAddr, ConfigError> {
+        self.bind_address.parse().map_err(|e| {
+            ConfigError::Invalid(vec![format!("bind_address '{}': {}", self.bind_address, e)])
+        })
+    }
+
+    pub(crate) fn allow_origin(&self) -> Result<AllowOrigin, String> {
+        if self.cors_origins.iter().any(|origin| origin == ANY_ORIGIN) {
+            if self.cors_origins.len() > 1 {
+                return Err(format!(
+                    "cors_origins: '{}' cannot be combined with other origins",
+                    ANY_ORIGIN
+                ));
+            }
+            return Ok(AllowOrigin::from(Any));
+        }
+
+        let origins = self
+            .cors_origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin)
+                    .map_err(|e| format!("cors_origins '{}': {}", origin, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AllowOrigin::list(origins))
+    }
+}
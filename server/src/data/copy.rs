@@ -0,0 +1,193 @@
+use super::deletion::get_child_ids;
+use super::names::{ensure_name_available, is_name_taken};
+use super::ranking::get_knowledge_with_context_ids;
+use super::{
+    ensure_fractals_exist, extract_uuid, get_fractal_by_id, get_fractals_by_ids,
+    insert_fractal_node, insert_has_child_edge, insert_has_context_edge, insert_in_context_edges,
+    insert_knowledge, uuid_list, with_transaction, DataError, Fractal, ParentLink,
+};
+use kuzu::{Connection, Value};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Highest number [`copy_subtree`] adds to a name before giving up.
+pub const MAX_COPY_NUMBER: u32 = 100;
+
+/// How the copies made by [`copy_subtree`] are named.
+#[derive(Debug, Clone)]
+pub struct CopyNaming {
+    /// New names for specific fractals of the subtree, keyed by their id.
+    pub renames: HashMap<Uuid, String>,
+    /// Appended to the name of every other fractal. When the result is taken by a sibling of the
+    /// copied subtree a number up to [`MAX_COPY_NUMBER`] is added, e.g. `Rust (copy) 2`.
+    pub suffix: String,
+}
+
+impl Default for CopyNaming {
+    fn default() -> Self {
+        CopyNaming {
+            renames: HashMap::new(),
+            suffix: " (copy)".to_string(),
+        }
+    }
+}
+
+/// Copies `id` and all of its descendants under `parent_id` in `context_id`, in one transaction.
+///
+/// The copies keep the `HAS_CHILD` edges between each other and their `HAS_CONTEXT` edges, and
+/// get copies of their knowledge. Contexts that are part of the subtree are replaced by their
+/// copies. Aliases are not copied. Fails with [`DataError::FractalAlreadyExists`] when an
+/// explicit rename is taken among its new siblings, or when every numbered name is. Returns the
+/// copies, the copy of `id` first.
+pub fn copy_subtree(
+    conn: &Connection,
+    id: &Uuid,
+    parent_id: &Uuid,
+    context_id: Option<&Uuid>,
+    naming: &CopyNaming,
+) -> Result<Vec<Fractal>, DataError> {
+    with_transaction(conn, |conn| {
+        let referenced: Vec<Uuid> = std::iter::once(*parent_id)
+            .chain(context_id.copied())
+            .collect();
+        ensure_fractals_exist(conn, &referenced)?;
+
+        let mut fractals = vec![get_fractal_by_id(conn, id)?];
+        let descendant_ids = get_descendant_ids(conn, id)?;
+        let mut descendants: HashMap<Uuid, Fractal> = get_fractals_by_ids(conn, &descendant_ids)?
+            .into_iter()
+            .map(|f| (f.id, f))
+            .collect();
+        for descendant_id in descendant_ids {
+            if let Some(fractal) = descendants.remove(&descendant_id) {
+                fractals.push(fractal);
+            }
+        }
+        let ids: Vec<Uuid> = fractals.iter().map(|f| f.id).collect();
+
+        let link = ParentLink {
+            parent_id: *parent_id,
+            context_id: context_id.copied(),
+        };
+        let mut copies = Vec::with_capacity(fractals.len());
+        let mut copy_ids = HashMap::new();
+        for (i, fractal) in fractals.iter().enumerate() {
+            // Only the copy of `id` joins existing siblings, the others are children of copies
+            let name = copy_name(conn, fractal, naming, (i == 0).then_some(&link))?;
+            let copy = insert_fractal_node(conn, &name, None)?;
+            copy_ids.insert(fractal.id, copy.id);
+            copies.push(copy);
+        }
+        let mapped = |id: &Uuid| *copy_ids.get(id).unwrap_or(id);
+
+        insert_has_child_edge(conn, parent_id, &copies[0].id, context_id)?;
+        for (parent, child, context) in get_inner_child_edges(conn, &ids)? {
+            let context = context.as_ref().map(mapped);
+            insert_has_child_edge(conn, &mapped(&parent), &mapped(&child), context.as_ref())?;
+        }
+        for (fractal, context) in get_context_edges(conn, &ids)? {
+            insert_has_context_edge(conn, &mapped(&fractal), &mapped(&context))?;
+        }
+
+        for fractal in &fractals {
+            for (knowledge, contexts) in get_knowledge_with_context_ids(conn, &fractal.id)? {
+                let copy = insert_knowledge(conn, &mapped(&fractal.id), &knowledge.content)?;
+                let contexts: Vec<Uuid> = contexts.iter().map(mapped).collect();
+                insert_in_context_edges(conn, &copy.id, &contexts)?;
+            }
+        }
+
+        Ok(copies)
+    })
+}
+
+/// Names the copy of `fractal`, checking against the siblings under `parent` when given. Names
+/// under a copied parent are checked when the edge to it is created.
+fn copy_name(
+    conn: &Connection,
+    fractal: &Fractal,
+    naming: &CopyNaming,
+    parent: Option<&ParentLink>,
+) -> Result<String, DataError> {
+    if let Some(rename) = naming.renames.get(&fractal.id) {
+        if parent.is_some() {
+            ensure_name_available(conn, rename, parent, None)?;
+        }
+        return Ok(rename.clone());
+    }
+
+    let base = format!("{}{}", fractal.name, naming.suffix);
+    let Some(parent) = parent else {
+        return Ok(base);
+    };
+    let numbered = (2..=MAX_COPY_NUMBER).map(|n| format!("{} {}", base, n));
+    for candidate in std::iter::once(base.clone()).chain(numbered) {
+        if !is_name_taken(conn, &candidate, Some(parent), None)? {
+            return Ok(candidate);
+        }
+    }
+    Err(DataError::FractalAlreadyExists(base))
+}
+
+/// Live descendants of `id`, nearest first, however deep they are.
+fn get_descendant_ids(conn: &Connection, id: &Uuid) -> Result<Vec<Uuid>, DataError> {
+    let mut seen = HashSet::from([*id]);
+    let mut descendants = Vec::new();
+    let mut frontier = vec![*id];
+    while !frontier.is_empty() {
+        frontier = get_child_ids(conn, &frontier)?
+            .into_iter()
+            .filter(|child_id| seen.insert(*child_id))
+            .collect();
+        frontier.sort();
+        descendants.extend(&frontier);
+    }
+    Ok(descendants)
+}
+
+/// `(parent id, child id, context id)` of every `HAS_CHILD` edge between two of `ids`.
+fn get_inner_child_edges(
+    conn: &Connection,
+    ids: &[Uuid],
+) -> Result<Vec<(Uuid, Uuid, Option<Uuid>)>, DataError> {
+    let query = "
+        MATCH (p:Fractal)-[r:HAS_CHILD]->(c:Fractal)
+        WHERE p.id IN $ids AND c.id IN $ids
+        RETURN p.id, c.id, r.context_id
+    ";
+    let params = vec![("ids", uuid_list(ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let context_id = match &row[2] {
+                Value::Null(_) => None,
+                value => Some(extract_uuid(value, "context_id")?),
+            };
+            Ok((
+                extract_uuid(&row[0], "id")?,
+                extract_uuid(&row[1], "id")?,
+                context_id,
+            ))
+        })
+        .collect()
+}
+
+/// `(fractal id, context id)` of every `HAS_CONTEXT` edge leaving one of `ids`.
+fn get_context_edges(conn: &Connection, ids: &[Uuid]) -> Result<Vec<(Uuid, Uuid)>, DataError> {
+    let query = "
+        MATCH (f:Fractal)-[:HAS_CONTEXT]->(c:Fractal)
+        WHERE f.id IN $ids AND c.deletedAt IS NULL
+        RETURN f.id, c.id
+    ";
+    let params = vec![("ids", uuid_list(ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| Ok((extract_uuid(&row[0], "id")?, extract_uuid(&row[1], "id")?)))
+        .collect()
+}
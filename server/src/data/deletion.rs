@@ -0,0 +1,301 @@
+use super::{
+    edge_context_condition, edge_params, extract_uuid, get_fractal_by_id, get_fractals_by_ids,
+    insert_has_child_edge, mark_deleted, row_to_knowledge, uuid_list, with_transaction, DataError,
+    Fractal, Knowledge, FRACTAL_ROOT_ID,
+};
+use kuzu::{Connection, Value};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// What happens to the children of a deleted fractal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeleteMode {
+    /// Refuse to delete a fractal that still has children.
+    #[default]
+    Restrict,
+    /// Link the children to every parent of the deleted fractal, keeping their contexts.
+    Reparent,
+    /// Also delete the descendants that have no parent outside of the deleted subtree.
+    Cascade,
+}
+
+/// Everything a deletion removes or relinks.
+#[derive(Debug, Clone, Default)]
+pub struct DeletionReport {
+    /// Deleted fractals, the requested one first.
+    pub fractals: Vec<Fractal>,
+    /// Knowledge owned by the deleted fractals.
+    pub knowledge: Vec<Knowledge>,
+    /// Children linked to the parents of the deleted fractal.
+    pub reparented: Vec<Fractal>,
+}
+
+/// A `HAS_CHILD` edge from the deleted fractal's parent `parent_id` to one of its children.
+struct Relink {
+    parent_id: Uuid,
+    child_id: Uuid,
+    context_id: Option<Uuid>,
+}
+
+/// Moves a fractal to the trash, handling its children according to `mode`.
+///
+/// The deleted fractals and their knowledge keep their edges and can be brought back together
+/// with [`restore_fractal`](super::restore_fractal). Root cannot be deleted. With `dry_run`
+/// nothing is changed and the report describes what the deletion would do.
+pub fn delete_fractal(
+    conn: &Connection,
+    id: &Uuid,
+    mode: DeleteMode,
+    dry_run: bool,
+    deleted_by: Option<&str>,
+) -> Result<DeletionReport, DataError> {
+    if *id == FRACTAL_ROOT_ID {
+        return Err(DataError::RootFractalProtected);
+    }
+
+    if dry_run {
+        return plan_deletion(conn, id, mode).map(|(report, _)| report);
+    }
+
+    with_transaction(conn, |conn| {
+        let (report, relinks) = plan_deletion(conn, id, mode)?;
+        let deletion_id = Uuid::new_v4();
+
+        for relink in &relinks {
+            let created = insert_has_child_edge(
+                conn,
+                &relink.parent_id,
+                &relink.child_id,
+                relink.context_id.as_ref(),
+            )?;
+            if created {
+                tag_relink(conn, relink, &deletion_id)?;
+            }
+        }
+
+        let knowledge_ids: Vec<Uuid> = report.knowledge.iter().map(|k| k.id).collect();
+        mark_deleted(conn, "Knowledge", &knowledge_ids, &deletion_id, deleted_by)?;
+
+        let fractal_ids: Vec<Uuid> = report.fractals.iter().map(|f| f.id).collect();
+        mark_deleted(conn, "Fractal", &fractal_ids, &deletion_id, deleted_by)?;
+
+        Ok(report)
+    })
+}
+
+/// Marks a freshly created relink with the deletion it belongs to, so that restoring the
+/// deletion removes it again. Links that existed before the deletion are left untouched.
+fn tag_relink(conn: &Connection, relink: &Relink, deletion_id: &Uuid) -> Result<(), DataError> {
+    let context_id = relink.context_id.as_ref();
+    let query = format!(
+        "
+        MATCH (parent:Fractal {{id: $parent_id}})-[r:HAS_CHILD]->(child:Fractal {{id: $child_id}})
+        WHERE {}
+        SET r.deletionId = $deletion_id
+        ",
+        edge_context_condition(context_id)
+    );
+    let mut params = edge_params(&relink.parent_id, &relink.child_id, context_id);
+    params.push(("deletion_id", Value::UUID(*deletion_id)));
+    let mut stmt = conn.prepare(&query)?;
+    conn.execute(&mut stmt, params)?;
+    Ok(())
+}
+
+fn plan_deletion(
+    conn: &Connection,
+    id: &Uuid,
+    mode: DeleteMode,
+) -> Result<(DeletionReport, Vec<Relink>), DataError> {
+    let fractal = get_fractal_by_id(conn, id)?;
+    let children = get_child_edges(conn, id)?;
+
+    let mut report = DeletionReport {
+        fractals: vec![fractal],
+        ..Default::default()
+    };
+    let mut relinks = Vec::new();
+
+    match mode {
+        DeleteMode::Restrict => {
+            let count = children
+                .iter()
+                .map(|(child_id, _)| child_id)
+                .collect::<HashSet<_>>()
+                .len();
+            if count > 0 {
+                return Err(DataError::FractalHasChildren {
+                    id: *id,
+                    children: count,
+                });
+            }
+        }
+        DeleteMode::Reparent => {
+            let mut parent_ids: Vec<Uuid> = get_parent_edges(conn, &[*id])?
+                .into_iter()
+                .map(|(parent_id, _)| parent_id)
+                .collect();
+            parent_ids.sort();
+            parent_ids.dedup();
+
+            let mut child_ids = Vec::new();
+            for (child_id, context_id) in children {
+                for parent_id in &parent_ids {
+                    relinks.push(Relink {
+                        parent_id: *parent_id,
+                        child_id,
+                        context_id,
+                    });
+                }
+                if !child_ids.contains(&child_id) {
+                    child_ids.push(child_id);
+                }
+            }
+            report.reparented = get_fractals_in_order(conn, &child_ids)?;
+        }
+        DeleteMode::Cascade => {
+            let subtree = get_subtree_only_descendants(conn, id)?;
+            report
+                .fractals
+                .extend(get_fractals_in_order(conn, &subtree)?);
+        }
+    }
+
+    let fractal_ids: Vec<Uuid> = report.fractals.iter().map(|f| f.id).collect();
+    report.knowledge = get_owned_knowledge(conn, &fractal_ids)?;
+
+    Ok((report, relinks))
+}
+
+/// Descendants of `id` whose parents all get deleted along with it, nearest first. Fractals
+/// already in the trash are neither followed nor counted as parents.
+fn get_subtree_only_descendants(conn: &Connection, id: &Uuid) -> Result<Vec<Uuid>, DataError> {
+    // Walked level by level without a depth limit, so no descendant is left behind unreachable.
+    // Links in different contexts can lead back up, which the seen set takes care of.
+    let mut seen = HashSet::from([*id, FRACTAL_ROOT_ID]);
+    let mut descendants = Vec::new();
+    let mut frontier = vec![*id];
+    while !frontier.is_empty() {
+        frontier = get_child_ids(conn, &frontier)?
+            .into_iter()
+            .filter(|child_id| seen.insert(*child_id))
+            .collect();
+        descendants.extend(&frontier);
+    }
+
+    let mut parents: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (parent_id, child_id) in get_parent_edges(conn, &descendants)? {
+        parents.entry(child_id).or_default().push(parent_id);
+    }
+
+    let mut deleted = HashSet::from([*id]);
+    let mut subtree = Vec::new();
+    loop {
+        let before = subtree.len();
+        for descendant in &descendants {
+            if deleted.contains(descendant) {
+                continue;
+            }
+            let only_inside = parents
+                .get(descendant)
+                .is_some_and(|p| p.iter().all(|parent| deleted.contains(parent)));
+            if only_inside {
+                deleted.insert(*descendant);
+                subtree.push(*descendant);
+            }
+        }
+        if subtree.len() == before {
+            return Ok(subtree);
+        }
+    }
+}
+
+/// Children of any of `ids` outside the trash, each listed once.
+pub(super) fn get_child_ids(conn: &Connection, ids: &[Uuid]) -> Result<Vec<Uuid>, DataError> {
+    let query = "
+        MATCH (p:Fractal)-[:HAS_CHILD]->(c:Fractal)
+        WHERE p.id IN $ids AND c.deletedAt IS NULL
+        RETURN DISTINCT c.id
+    ";
+    let params = vec![("ids", uuid_list(ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| extract_uuid(&row[0], "id"))
+        .collect()
+}
+
+/// `(child id, context id)` of every `HAS_CHILD` edge leaving `id`.
+fn get_child_edges(conn: &Connection, id: &Uuid) -> Result<Vec<(Uuid, Option<Uuid>)>, DataError> {
+    let query = "
+        MATCH (f:Fractal {id: $id})-[r:HAS_CHILD]->(c:Fractal)
+        WHERE c.deletedAt IS NULL
+        RETURN c.id, r.context_id
+    ";
+    let params = vec![("id", Value::UUID(*id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let context_id = match &row[1] {
+                Value::Null(_) => None,
+                value => Some(extract_uuid(value, "context_id")?),
+            };
+            Ok((extract_uuid(&row[0], "id")?, context_id))
+        })
+        .collect()
+}
+
+/// `(parent id, child id)` of every `HAS_CHILD` edge entering one of `ids`.
+fn get_parent_edges(conn: &Connection, ids: &[Uuid]) -> Result<Vec<(Uuid, Uuid)>, DataError> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let query = "
+        MATCH (p:Fractal)-[:HAS_CHILD]->(c:Fractal)
+        WHERE c.id IN $ids AND p.deletedAt IS NULL
+        RETURN p.id, c.id
+    ";
+    let params = vec![("ids", uuid_list(ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| Ok((extract_uuid(&row[0], "id")?, extract_uuid(&row[1], "id")?)))
+        .collect()
+}
+
+fn get_fractals_in_order(conn: &Connection, ids: &[Uuid]) -> Result<Vec<Fractal>, DataError> {
+    let mut found: HashMap<Uuid, Fractal> = get_fractals_by_ids(conn, ids)?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+
+    Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
+}
+
+fn get_owned_knowledge(
+    conn: &Connection,
+    fractal_ids: &[Uuid],
+) -> Result<Vec<Knowledge>, DataError> {
+    let query = "
+        MATCH (f:Fractal)-[:HAS_KNOWLEDGE]->(k:Knowledge)
+        WHERE f.id IN $ids AND k.deletedAt IS NULL
+        RETURN k.id, k.content, k.createdAt, k.updatedAt
+        ORDER BY k.createdAt, k.id
+    ";
+    let params = vec![("ids", uuid_list(fractal_ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| row_to_knowledge(&row))
+        .collect()
+}
@@ -0,0 +1,216 @@
+use super::{
+    extract_uuid, get_fractal_by_id, get_fractals_by_ids, uuid_list, DataError, Fractal,
+    MAX_HIERARCHY_DEPTH,
+};
+use kuzu::{Connection, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A `HAS_CHILD` edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HierarchyEdge {
+    pub parent_id: Uuid,
+    pub child_id: Uuid,
+    pub context_id: Option<Uuid>,
+}
+
+/// A fractal reached while walking the hierarchy, once for every edge it was reached through.
+#[derive(Debug, Clone)]
+pub struct HierarchyEntry {
+    pub fractal: Fractal,
+    /// Distance from the starting fractal, `1` for its direct children or parents.
+    pub depth: usize,
+    pub edge: HierarchyEdge,
+}
+
+/// Which way [`walk_hierarchy`] follows `HAS_CHILD` edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Down,
+    Up,
+}
+
+/// Everything below `id` up to `max_depth` levels, capped at [`MAX_HIERARCHY_DEPTH`].
+///
+/// Like `children`, only edges in exactly `context_id` are followed when it is given. A fractal
+/// with several parents in the result is listed once per edge. Entries are ordered by depth,
+/// then name.
+pub fn get_descendants(
+    conn: &Connection,
+    id: &Uuid,
+    max_depth: usize,
+    context_id: Option<&Uuid>,
+) -> Result<Vec<HierarchyEntry>, DataError> {
+    walk_hierarchy(conn, id, max_depth, context_id, Direction::Down)
+}
+
+/// Everything above `id` up to `max_depth` levels, capped at [`MAX_HIERARCHY_DEPTH`], in any
+/// context. Ordered like [`get_descendants`].
+pub fn get_ancestors(
+    conn: &Connection,
+    id: &Uuid,
+    max_depth: usize,
+) -> Result<Vec<HierarchyEntry>, DataError> {
+    walk_hierarchy(conn, id, max_depth, None, Direction::Up)
+}
+
+fn walk_hierarchy(
+    conn: &Connection,
+    id: &Uuid,
+    max_depth: usize,
+    context_id: Option<&Uuid>,
+    direction: Direction,
+) -> Result<Vec<HierarchyEntry>, DataError> {
+    get_fractal_by_id(conn, id)?;
+
+    let max_depth = max_depth.min(MAX_HIERARCHY_DEPTH);
+    if max_depth == 0 {
+        return Ok(vec![]);
+    }
+
+    let mut depths = get_reachable(conn, id, max_depth, context_id, direction)?;
+    let reached: Vec<Uuid> = depths.keys().copied().collect();
+    depths.insert(*id, 0);
+    let walked: Vec<Uuid> = depths.keys().copied().collect();
+    let fractals: HashMap<Uuid, Fractal> = get_fractals_by_ids(conn, &reached)?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+
+    let mut entries = Vec::new();
+    for edge in get_edges_between(conn, &walked, context_id)? {
+        let (from, to) = match direction {
+            Direction::Down => (edge.parent_id, edge.child_id),
+            Direction::Up => (edge.child_id, edge.parent_id),
+        };
+        let (Some(from_depth), Some(fractal)) = (depths.get(&from), fractals.get(&to)) else {
+            continue;
+        };
+        // Edges back to the starting fractal are only possible across contexts
+        if to == *id || from_depth + 1 > max_depth {
+            continue;
+        }
+        entries.push(HierarchyEntry {
+            fractal: fractal.clone(),
+            depth: from_depth + 1,
+            edge,
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        a.depth
+            .cmp(&b.depth)
+            .then_with(|| a.fractal.name.cmp(&b.fractal.name))
+            .then_with(|| a.edge.parent_id.cmp(&b.edge.parent_id))
+            .then_with(|| a.edge.child_id.cmp(&b.edge.child_id))
+    });
+    Ok(entries)
+}
+
+/// Maps every fractal reachable from `id` within `max_depth` edges to its shortest distance.
+fn get_reachable(
+    conn: &Connection,
+    id: &Uuid,
+    max_depth: usize,
+    context_id: Option<&Uuid>,
+    direction: Direction,
+) -> Result<HashMap<Uuid, usize>, DataError> {
+    // Walked level by level, so every fractal is visited once however many paths lead to it
+    let mut depths = HashMap::new();
+    let mut frontier = vec![*id];
+    for depth in 1..=max_depth {
+        if frontier.is_empty() {
+            break;
+        }
+        frontier = get_neighbour_ids(conn, &frontier, context_id, direction)?
+            .into_iter()
+            .filter(|neighbour| *neighbour != *id && !depths.contains_key(neighbour))
+            .collect();
+        for neighbour in &frontier {
+            depths.insert(*neighbour, depth);
+        }
+    }
+    Ok(depths)
+}
+
+/// Children or parents of any of `ids` outside the trash, each listed once.
+fn get_neighbour_ids(
+    conn: &Connection,
+    ids: &[Uuid],
+    context_id: Option<&Uuid>,
+    direction: Direction,
+) -> Result<Vec<Uuid>, DataError> {
+    let pattern = match direction {
+        Direction::Down => "(f:Fractal)-[r:HAS_CHILD]->(d:Fractal)",
+        Direction::Up => "(d:Fractal)-[r:HAS_CHILD]->(f:Fractal)",
+    };
+    let condition = match context_id {
+        Some(_) => "AND r.context_id = $context_id",
+        None => "",
+    };
+    let query = format!(
+        "
+        MATCH {}
+        WHERE f.id IN $ids AND d.deletedAt IS NULL {}
+        RETURN DISTINCT d.id
+        ",
+        pattern, condition
+    );
+
+    let mut params = vec![("ids", uuid_list(ids))];
+    if let Some(context_id) = context_id {
+        params.push(("context_id", Value::UUID(*context_id)));
+    }
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| extract_uuid(&row[0], "id"))
+        .collect()
+}
+
+/// Every `HAS_CHILD` edge between two of `ids`, only in `context_id` when given.
+fn get_edges_between(
+    conn: &Connection,
+    ids: &[Uuid],
+    context_id: Option<&Uuid>,
+) -> Result<Vec<HierarchyEdge>, DataError> {
+    let query = match context_id {
+        Some(_) => {
+            "
+            MATCH (p:Fractal)-[r:HAS_CHILD]->(c:Fractal)
+            WHERE p.id IN $ids AND c.id IN $ids AND r.context_id = $context_id
+            RETURN p.id, c.id, r.context_id
+        "
+        }
+        None => {
+            "
+            MATCH (p:Fractal)-[r:HAS_CHILD]->(c:Fractal)
+            WHERE p.id IN $ids AND c.id IN $ids
+            RETURN p.id, c.id, r.context_id
+        "
+        }
+    };
+    let mut params = vec![("ids", uuid_list(ids))];
+    if let Some(context_id) = context_id {
+        params.push(("context_id", Value::UUID(*context_id)));
+    }
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let context_id = match &row[2] {
+                Value::Null(_) => None,
+                value => Some(extract_uuid(value, "context_id")?),
+            };
+            Ok(HierarchyEdge {
+                parent_id: extract_uuid(&row[0], "id")?,
+                child_id: extract_uuid(&row[1], "id")?,
+                context_id,
+            })
+        })
+        .collect()
+}
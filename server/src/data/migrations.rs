@@ -0,0 +1,180 @@
+use super::{with_transaction, DataError};
+use kuzu::{Connection, Value};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+
+/// A single, ordered step of the database schema.
+///
+/// Migrations are applied in ascending `version` order and each one runs in its own
+/// transaction together with the record of it being applied, so a failing statement leaves the
+/// schema at the previous version.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub statements: &'static [&'static str],
+}
+
+/// Every migration known to this build, oldest first. Append new migrations to the end and
+/// never edit one that has already shipped.
+///
+/// Scoping name uniqueness to a parent and context needed no migration: names that were unique
+/// across the whole graph are unique under every parent too. Lookups by name alone can become
+/// ambiguous once same-named fractals are added, though.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Initial Fractal and Knowledge schema",
+        // `IF NOT EXISTS` lets databases created before migrations existed be adopted as v1.
+        statements: &[
+            "CREATE NODE TABLE IF NOT EXISTS Fractal (
+            id UUID,
+            name STRING,
+            createdAt TIMESTAMP,
+            updatedAt TIMESTAMP,
+            PRIMARY KEY (id)
+        )",
+            "CREATE NODE TABLE IF NOT EXISTS Knowledge (
+            id UUID,
+            content STRING,
+            createdAt TIMESTAMP,
+            updatedAt TIMESTAMP,
+            PRIMARY KEY (id)
+        )",
+            "CREATE REL TABLE IF NOT EXISTS HAS_CHILD (
+            FROM Fractal
+            TO Fractal,
+            context_id UUID
+        )",
+            "CREATE REL TABLE IF NOT EXISTS HAS_CONTEXT(FROM Fractal TO Fractal)",
+            "CREATE REL TABLE IF NOT EXISTS HAS_KNOWLEDGE(FROM Fractal TO Knowledge)",
+            "CREATE REL TABLE IF NOT EXISTS IN_CONTEXT(FROM Knowledge TO Fractal)",
+        ],
+    },
+    Migration {
+        version: 2,
+        description: "Soft delete for fractals and knowledge",
+        // Items deleted together share a `deletionId` so they can be restored together.
+        statements: &[
+            "ALTER TABLE Fractal ADD deletedAt TIMESTAMP",
+            "ALTER TABLE Fractal ADD deletedBy STRING",
+            "ALTER TABLE Fractal ADD deletionId UUID",
+            "ALTER TABLE Knowledge ADD deletedAt TIMESTAMP",
+            "ALTER TABLE Knowledge ADD deletedBy STRING",
+            "ALTER TABLE Knowledge ADD deletionId UUID",
+        ],
+    },
+    Migration {
+        version: 3,
+        description: "Aliases for fractals",
+        statements: &["ALTER TABLE Fractal ADD aliases STRING[]"],
+    },
+    Migration {
+        version: 4,
+        description: "Deletion ids on links added by reparenting deletes",
+        // Restoring a deletion removes the `HAS_CHILD` edges tagged with its `deletionId`.
+        statements: &["ALTER TABLE HAS_CHILD ADD deletionId UUID"],
+    },
+];
+
+pub fn latest_schema_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Returns the version of the last migration applied to the database, or `0` for an empty one.
+pub fn get_schema_version(conn: &Connection) -> Result<u32, DataError> {
+    create_schema_migration_table(conn)?;
+
+    let result = conn.query("MATCH (m:SchemaMigration) RETURN max(m.version)")?;
+
+    match result.into_iter().next().as_deref() {
+        Some([Value::Int64(version)]) => u32::try_from(*version)
+            .map_err(|_| DataError::InvalidData(format!("Invalid schema version {}", version))),
+        Some([Value::Null(_)]) | None => Ok(0),
+        Some(row) => Err(DataError::InvalidData(format!(
+            "Expected schema version, found {:?}",
+            row
+        ))),
+    }
+}
+
+/// Brings the database schema up to the newest version in `migrations`.
+///
+/// Returns the resulting schema version. Fails with [`DataError::SchemaVersionTooNew`] without
+/// touching the database if it was written by a newer build.
+pub fn migrate(conn: &Connection, migrations: &[Migration]) -> Result<u32, DataError> {
+    validate_migrations(migrations)?;
+
+    let current = get_schema_version(conn)?;
+    let latest = migrations.last().map_or(0, |m| m.version);
+
+    if current > latest {
+        return Err(DataError::SchemaVersionTooNew {
+            found: current,
+            supported: latest,
+        });
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        println!(
+            "Applying migration {}: {}",
+            migration.version, migration.description
+        );
+        with_transaction(conn, |conn| {
+            for statement in migration.statements {
+                conn.query(statement)?;
+            }
+            record_migration(conn, migration)
+        })?;
+    }
+
+    Ok(latest.max(current))
+}
+
+fn validate_migrations(migrations: &[Migration]) -> Result<(), DataError> {
+    for (expected, migration) in (1..).zip(migrations) {
+        if migration.version != expected {
+            return Err(DataError::InvalidData(format!(
+                "Migration '{}' has version {}, expected {}",
+                migration.description, migration.version, expected
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn create_schema_migration_table(conn: &Connection) -> Result<(), DataError> {
+    conn.query(
+        "CREATE NODE TABLE IF NOT EXISTS SchemaMigration (
+            version INT64,
+            description STRING,
+            appliedAt TIMESTAMP,
+            PRIMARY KEY (version)
+        )",
+    )?;
+    Ok(())
+}
+
+fn record_migration(conn: &Connection, migration: &Migration) -> Result<(), DataError> {
+    let query = "
+        CREATE (:SchemaMigration {
+            version: $version,
+            description: $description,
+            appliedAt: $datetime
+        })
+    ";
+    let params = vec![
+        ("version", Value::Int64(migration.version.into())),
+        (
+            "description",
+            Value::String(migration.description.to_string()),
+        ),
+        (
+            "datetime",
+            Value::Timestamp(OffsetDateTime::from(SystemTime::now())),
+        ),
+    ];
+    let mut stmt = conn.prepare(query)?;
+    conn.execute(&mut stmt, params)?;
+    Ok(())
+}
@@ -1,19 +1,76 @@
 use chrono::{DateTime, Utc};
 use kuzu::{Connection, Database, Error as KuzuError, LogicalType, SystemConfig, Value};
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
 use std::time::SystemTime;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+mod copy;
+pub use copy::*;
+mod deletion;
+pub use deletion::*;
+mod hierarchy;
+pub use hierarchy::*;
+mod migrations;
+pub use migrations::*;
+mod names;
+pub use names::*;
+mod paths;
+pub use paths::*;
+mod ranking;
+pub use ranking::*;
+mod subgraph;
+pub use subgraph::*;
+mod trash;
+pub use trash::*;
+
 #[derive(Debug, thiserror::Error)]
 pub enum DataError {
     #[error("Database error: {0}")]
     Database(#[from] KuzuError),
+    #[error("Failed to open database at '{path}': {reason}")]
+    DatabaseUnavailable { path: String, reason: String },
     #[error("Fractal with name '{0}' already exists")]
     FractalAlreadyExists(String),
     #[error("Fractal not found: {0}")]
     FractalNotFound(String),
+    #[error("{count} fractals are named '{name}'")]
+    AmbiguousName { name: String, count: usize },
+    #[error("Alias '{0}' must not be empty or contain '{sep}'", sep = PATH_SEPARATOR)]
+    InvalidAlias(String),
+    #[error("Fractals not found: {}", format_ids(.0))]
+    FractalsNotFound(Vec<Uuid>),
+    #[error("Fractal {0} cannot be its own child")]
+    SelfRelation(Uuid),
+    #[error("Adding {child_id} as a child of {parent_id} would create a cycle")]
+    RelationCycle { parent_id: Uuid, child_id: Uuid },
+    #[error("Fractal {child_id} is not a child of {parent_id}")]
+    RelationNotFound { parent_id: Uuid, child_id: Uuid },
+    #[error("'{segment}' in path '{path}' is not a child of '{parent}' in this context")]
+    PathSegmentNotFound {
+        path: String,
+        segment: String,
+        parent: String,
+    },
+    #[error("The Root fractal cannot be deleted or renamed")]
+    RootFractalProtected,
+    #[error("Fractal {id} still has {children} children")]
+    FractalHasChildren { id: Uuid, children: usize },
+    #[error("Knowledge not found: {0}")]
+    KnowledgeNotFound(String),
     #[error("Invalid data: {0}")]
     InvalidData(String),
+    #[error("Fractal {id} was modified at {actual}, expected {expected}")]
+    StaleUpdate {
+        id: Uuid,
+        expected: DateTime<Utc>,
+        actual: DateTime<Utc>,
+    },
+    #[error(
+        "Database schema version {found} is newer than the latest supported version {supported}"
+    )]
+    SchemaVersionTooNew { found: u32, supported: u32 },
 }
 
 #[derive(Debug, Clone)]
@@ -28,57 +85,126 @@ pub struct Fractal {
 pub struct Knowledge {
     pub id: Uuid,
     pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A `HAS_CHILD` edge to create from `parent_id`, optionally only valid in `context_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParentLink {
+    pub parent_id: Uuid,
+    pub context_id: Option<Uuid>,
+}
+
+/// Changes to apply to a fractal, `None` fields are left untouched.
+#[derive(Debug, Clone, Default)]
+pub struct FractalUpdate {
+    pub name: Option<String>,
 }
 
 pub const FRACTAL_ROOT_ID: Uuid = Uuid::nil();
 
-pub fn create_db(_db_path: &str) -> Result<Database, DataError> {
-    Database::new("", SystemConfig::default()).map_err(DataError::from)
+/// Longest `HAS_CHILD` chain followed when walking the hierarchy.
+pub const MAX_HIERARCHY_DEPTH: usize = 30;
+
+/// Path that opens a throwaway in-memory database instead of an on-disk one.
+pub const IN_MEMORY_DB_PATH: &str = ":memory:";
+
+/// Opens the database stored in the `db_path` directory, creating it if it does not exist yet.
+///
+/// Pass [`IN_MEMORY_DB_PATH`] to get an in-memory database that is discarded on drop.
+pub fn create_db(db_path: &str) -> Result<Database, DataError> {
+    if db_path == IN_MEMORY_DB_PATH {
+        return create_in_memory_db();
+    }
+
+    let unavailable = |reason: String| DataError::DatabaseUnavailable {
+        path: db_path.to_string(),
+        reason,
+    };
+
+    if db_path.trim().is_empty() {
+        return Err(unavailable(format!(
+            "path is empty, use '{}' for an in-memory database",
+            IN_MEMORY_DB_PATH
+        )));
+    }
+
+    let path = Path::new(db_path);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| unavailable(e.to_string()))?;
+    }
+
+    // Kuzu reports a locked (already opened by another process) or corrupt database directory
+    // as an error from the constructor, so surface it together with the offending path.
+    Database::new(path, SystemConfig::default()).map_err(|e| unavailable(e.to_string()))
+}
+
+pub fn create_in_memory_db() -> Result<Database, DataError> {
+    Database::in_memory(SystemConfig::default()).map_err(DataError::from)
 }
 
-pub fn create_connection(db: &Database) -> Result<Connection, DataError> {
+pub fn create_connection(db: &Database) -> Result<Connection<'_>, DataError> {
     Connection::new(db).map_err(DataError::from)
 }
 
 pub fn init_database(conn: &Connection) -> Result<(), DataError> {
     println!("Initializing database...");
 
-    let create_tables = [
-        "CREATE NODE TABLE IF NOT EXISTS Fractal (
-            id UUID,
-            name STRING,
-            createdAt TIMESTAMP,
-            updatedAt TIMESTAMP,
-            PRIMARY KEY (id)
-        )",
-        "CREATE NODE TABLE IF NOT EXISTS Knowledge (
-            id UUID,
-            content STRING,
-            createdAt TIMESTAMP,
-            updatedAt TIMESTAMP,
-            PRIMARY KEY (id)
-        )",
-        "CREATE REL TABLE IF NOT EXISTS HAS_CHILD (
-            FROM Fractal
-            TO Fractal,
-            context_id UUID
-        )",
-        "CREATE REL TABLE IF NOT EXISTS HAS_CONTEXT(FROM Fractal TO Fractal)",
-        "CREATE REL TABLE IF NOT EXISTS HAS_KNOWLEDGE(FROM Fractal TO Knowledge)",
-        "CREATE REL TABLE IF NOT EXISTS IN_CONTEXT(FROM Knowledge TO Fractal)",
-    ];
-
-    for query in create_tables.iter() {
-        conn.query(query)?;
-    }
+    let version = migrate(conn, MIGRATIONS)?;
 
-    println!("Database tables created.");
+    println!("Database schema is at version {}.", version);
 
     println!("Database initialization completed.");
     Ok(())
 }
 
+/// Held for the whole of every write transaction started by this process.
+///
+/// Kuzu fails instead of waiting when a second write transaction is begun, so writers queue up
+/// here. The lock is shared by all databases the process opens, which only matters for tests.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Runs `f` inside a single write transaction, committing on success and rolling back on error.
+///
+/// Writes run through here are serialized: a check made inside `f` (e.g. for an existing name)
+/// still holds when `f` writes. Transactions do not nest, so `f` must only call the
+/// non-transactional helpers.
+pub fn with_transaction<T>(
+    conn: &Connection,
+    f: impl FnOnce(&Connection) -> Result<T, DataError>,
+) -> Result<T, DataError> {
+    // A poisoned lock only means another writer panicked, its transaction is gone with it
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    conn.query("BEGIN TRANSACTION")?;
+
+    match f(conn) {
+        Ok(value) => {
+            conn.query("COMMIT")?;
+            Ok(value)
+        }
+        Err(e) => {
+            // Kuzu already rolls the transaction back when one of its statements fails, in which
+            // case there is nothing left to roll back and the original error is what matters.
+            let _ = conn.query("ROLLBACK");
+            Err(e)
+        }
+    }
+}
+
 pub fn setup_example_graph(conn: &Connection) -> Result<(), DataError> {
+    with_transaction(conn, insert_example_graph)?;
+
+    println!("Root fractal created.");
+    Ok(())
+}
+
+fn insert_example_graph(conn: &Connection) -> Result<(), DataError> {
+    let create_fractal =
+        |conn: &Connection, name: &str, parent_id: Option<&Uuid>, context_id: Option<&Uuid>| {
+            insert_fractal(conn, name, parent_id, context_id, None)
+        };
+
     // Create Fractal nodes
     let programming = create_fractal(conn, "Programming", Some(&FRACTAL_ROOT_ID), None)?;
     let python = create_fractal(
@@ -96,8 +222,8 @@ pub fn setup_example_graph(conn: &Connection) -> Result<(), DataError> {
         Some(&FRACTAL_ROOT_ID),
     )?;
 
-    add_has_child_edge(conn, &python.id, &string.id, Some(&programming.id))?;
-    add_has_child_edge(conn, &rust.id, &string.id, Some(&programming.id))?;
+    insert_has_child_edge(conn, &python.id, &string.id, Some(&programming.id))?;
+    insert_has_child_edge(conn, &rust.id, &string.id, Some(&programming.id))?;
 
     // Create specific child relationships with contexts
     // Python -> String -> .count()
@@ -114,11 +240,45 @@ pub fn setup_example_graph(conn: &Connection) -> Result<(), DataError> {
     // Rust -> String -> &str
     let _amp_str = create_fractal(conn, "&str", Some(&string.id), Some(&rust.id))?;
 
+    // Programming -> Rust -> Ownership
+    let ownership = create_fractal(conn, "Ownership", Some(&rust.id), Some(&programming.id))?;
+
     // C -> String has no children or could have specific children if needed
-    println!("Root fractal created.");
+
+    // Knowledge about String, from general to context specific
+    let add_knowledge = |conn: &Connection, content: &str, context_ids: &[Uuid]| {
+        let knowledge = insert_knowledge(conn, &string.id, content)?;
+        insert_in_context_edges(conn, &knowledge.id, context_ids)
+    };
+    add_knowledge(
+        conn,
+        "A string is a sequence of characters.",
+        &[programming.id],
+    )?;
+    add_knowledge(
+        conn,
+        "Python strings are immutable sequences of Unicode code points.",
+        &[python.id],
+    )?;
+    add_knowledge(
+        conn,
+        "Rust's String is an owned, growable UTF-8 buffer.",
+        &[rust.id],
+    )?;
+    add_knowledge(
+        conn,
+        "Moving a String transfers ownership of its heap buffer instead of copying it.",
+        &[rust.id, ownership.id],
+    )?;
+
     Ok(())
 }
 
+/// Creates a fractal, and its `HAS_CHILD` edge from `parent_id` if given, in one transaction.
+///
+/// Fails with [`DataError::FractalAlreadyExists`] if the name is taken among the children of
+/// `parent_id` in `context_id`, or among the fractals without a parent, including by a fractal
+/// created concurrently on another connection.
 pub fn create_fractal_raw(
     conn: &Connection,
     name: &str,
@@ -126,10 +286,39 @@ pub fn create_fractal_raw(
     context_id: Option<&Uuid>,
     uuid: Option<Uuid>,
 ) -> Result<Fractal, DataError> {
-    if get_fractal_by_name(conn, name).is_ok() {
-        return Err(DataError::FractalAlreadyExists(name.to_string()));
+    with_transaction(conn, |conn| {
+        insert_fractal(conn, name, parent_id, context_id, uuid)
+    })
+}
+
+fn insert_fractal(
+    conn: &Connection,
+    name: &str,
+    parent_id: Option<&Uuid>,
+    context_id: Option<&Uuid>,
+    uuid: Option<Uuid>,
+) -> Result<Fractal, DataError> {
+    if parent_id.is_none() {
+        ensure_name_available(conn, name, None, None)?;
+    }
+    for id in parent_id.into_iter().chain(context_id) {
+        ensure_fractal_exists(conn, id)?;
+    }
+
+    let fractal = insert_fractal_node(conn, name, uuid)?;
+    if let Some(parent_id) = parent_id {
+        insert_has_child_edge(conn, parent_id, &fractal.id, context_id)?;
     }
 
+    Ok(fractal)
+}
+
+/// Creates just the node, the caller links it and checks its name.
+fn insert_fractal_node(
+    conn: &Connection,
+    name: &str,
+    uuid: Option<Uuid>,
+) -> Result<Fractal, DataError> {
     let query = "
         CREATE (f:Fractal {
             id: $uuid,
@@ -153,17 +342,11 @@ pub fn create_fractal_raw(
     let mut stmt = conn.prepare(query)?;
     let result = conn.execute(&mut stmt, params)?;
 
-    let fractal = result
+    result
         .into_iter()
         .next()
         .ok_or_else(|| DataError::InvalidData("Failed to create fractal".to_string()))
-        .and_then(|row| row_to_fractal(&row))?;
-
-    if let Some(parent_id) = parent_id {
-        add_has_child_edge(conn, parent_id, &fractal.id, context_id)?;
-    }
-
-    Ok(fractal)
+        .and_then(|row| row_to_fractal(&row))
 }
 
 pub fn create_fractal(
@@ -175,12 +358,92 @@ pub fn create_fractal(
     create_fractal_raw(conn, name, parent_id, context_id, None)
 }
 
+/// Creates a fractal under every one of `parents`, with `HAS_CONTEXT` edges to `contexts`, in one
+/// transaction.
+///
+/// Fails with [`DataError::FractalsNotFound`] listing every parent or context id that does not
+/// exist, nothing is created in that case. Duplicate links are only created once.
+pub fn create_fractal_with_parents(
+    conn: &Connection,
+    name: &str,
+    parents: &[ParentLink],
+    contexts: &[Uuid],
+) -> Result<Fractal, DataError> {
+    let mut links = Vec::with_capacity(parents.len());
+    for link in parents {
+        if !links.contains(link) {
+            links.push(*link);
+        }
+    }
+
+    let mut referenced = Vec::new();
+    for link in &links {
+        for id in std::iter::once(link.parent_id).chain(link.context_id) {
+            if !referenced.contains(&id) {
+                referenced.push(id);
+            }
+        }
+    }
+    for id in contexts {
+        if !referenced.contains(id) {
+            referenced.push(*id);
+        }
+    }
+
+    with_transaction(conn, |conn| {
+        ensure_fractals_exist(conn, &referenced)?;
+        if links.is_empty() {
+            ensure_name_available(conn, name, None, None)?;
+        }
+
+        let fractal = insert_fractal_node(conn, name, None)?;
+        for link in &links {
+            insert_has_child_edge(conn, &link.parent_id, &fractal.id, link.context_id.as_ref())?;
+        }
+        for context_id in contexts {
+            insert_has_context_edge(conn, &fractal.id, context_id)?;
+        }
+
+        Ok(fractal)
+    })
+}
+
+/// Links `child_id` under `parent_id` in `context_id`.
+///
+/// Returns `false` without creating anything when the same link already exists. Fails with
+/// [`DataError::SelfRelation`] or [`DataError::RelationCycle`] when the link would make a fractal
+/// its own ancestor in that context.
 pub fn add_has_child_edge(
     conn: &Connection,
     parent_id: &Uuid,
     child_id: &Uuid,
     context_id: Option<&Uuid>,
-) -> Result<(), DataError> {
+) -> Result<bool, DataError> {
+    with_transaction(conn, |conn| {
+        insert_has_child_edge(conn, parent_id, child_id, context_id)
+    })
+}
+
+fn insert_has_child_edge(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    ensure_edge_endpoints_exist(conn, parent_id, child_id)?;
+    if let Some(context_id) = context_id {
+        ensure_fractal_exists(conn, context_id)?;
+    }
+    ensure_no_cycle(conn, parent_id, child_id, context_id)?;
+    if has_child_edge_exists(conn, parent_id, child_id, context_id)? {
+        return Ok(false);
+    }
+    let link = ParentLink {
+        parent_id: *parent_id,
+        context_id: context_id.copied(),
+    };
+    ensure_names_available_under(conn, child_id, &link)?;
+
     println!("Adding has_child edge");
     let query = "
         MATCH (parent:Fractal {id: $parent_id}), (child:Fractal {id: $child_id})
@@ -188,55 +451,452 @@ pub fn add_has_child_edge(
             context_id: $context_id
         }]->(child)
         ";
-    let context_value = match context_id {
-        Some(id) => Value::UUID(*id),
-        None => Value::Null(LogicalType::UUID),
-    };
     let params = vec![
         ("parent_id", Value::UUID(*parent_id)),
         ("child_id", Value::UUID(*child_id)),
-        ("context_id", context_value),
+        ("context_id", optional_uuid(context_id)),
     ];
     let mut stmt = conn.prepare(query)?;
     conn.execute(&mut stmt, params)?;
 
+    Ok(true)
+}
+
+fn has_child_edge_exists(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    let query = format!(
+        "
+        MATCH (parent:Fractal {{id: $parent_id}})-[r:HAS_CHILD]->(child:Fractal {{id: $child_id}})
+        WHERE {}
+        RETURN count(r) > 0 as found
+        ",
+        edge_context_condition(context_id)
+    );
+    let params = edge_params(parent_id, child_id, context_id);
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    extract_flag(
+        result.into_iter().next(),
+        "Failed to look up has_child edge",
+    )
+}
+
+/// Fails when linking `child_id` under `parent_id` in `context_id` would make a fractal its own
+/// ancestor.
+///
+/// Edges without a context apply in every context, so a link in a context is checked against
+/// the edges of that context plus those without one. A link without a context is checked
+/// against every context at once.
+fn ensure_no_cycle(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<(), DataError> {
+    if parent_id == child_id {
+        return Err(DataError::SelfRelation(*parent_id));
+    }
+
+    if has_descendant_in_context(conn, child_id, parent_id, context_id)? {
+        return Err(DataError::RelationCycle {
+            parent_id: *parent_id,
+            child_id: *child_id,
+        });
+    }
+
     Ok(())
 }
 
+/// Whether `descendant_id` can be reached from `ancestor_id` following `HAS_CHILD` edges that
+/// apply in `context_id`, or in any single context when it is `None`.
+fn has_descendant_in_context(
+    conn: &Connection,
+    ancestor_id: &Uuid,
+    descendant_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    let (edge_filter, path_filter) = match context_id {
+        Some(_) => (
+            " (r, n | WHERE r.context_id IS NULL OR r.context_id = $context_id)",
+            "",
+        ),
+        // Every edge of the path has to apply in the same context, which holds when at most one
+        // context shows up on it
+        None => (
+            "",
+            "WHERE list_unique(properties(rels(e), 'context_id')) <= 1",
+        ),
+    };
+    let query = format!(
+        "
+        MATCH (a:Fractal {{id: $ancestor_id}})-[e:HAS_CHILD*1..{}{}]->(d:Fractal {{id: $descendant_id}})
+        {}
+        RETURN count(*) > 0 as found
+        ",
+        MAX_HIERARCHY_DEPTH, edge_filter, path_filter
+    );
+    let mut params = vec![
+        ("ancestor_id", Value::UUID(*ancestor_id)),
+        ("descendant_id", Value::UUID(*descendant_id)),
+    ];
+    if let Some(context_id) = context_id {
+        params.push(("context_id", Value::UUID(*context_id)));
+    }
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    extract_flag(result.into_iter().next(), "Failed to look up descendants")
+}
+
+/// Removes the `HAS_CHILD` edge from `parent_id` to `child_id` in `context_id`, where `None`
+/// only matches an edge without a context. Returns whether an edge was removed.
+pub fn remove_has_child_edge(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    with_transaction(conn, |conn| {
+        ensure_edge_endpoints_exist(conn, parent_id, child_id)?;
+        delete_has_child_edge(conn, parent_id, child_id, context_id)
+    })
+}
+
+fn delete_has_child_edge(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    let query = format!(
+        "
+        MATCH (parent:Fractal {{id: $parent_id}})-[r:HAS_CHILD]->(child:Fractal {{id: $child_id}})
+        WHERE {}
+        DELETE r
+        RETURN count(r) > 0 as removed
+        ",
+        edge_context_condition(context_id)
+    );
+    let params = edge_params(parent_id, child_id, context_id);
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    extract_flag(result.into_iter().next(), "Failed to remove has_child edge")
+}
+
+/// Moves the `HAS_CHILD` edge from `parent_id` to `child_id` in `context_id` to
+/// `new_context_id`. Returns whether an edge was updated.
+pub fn update_has_child_edge_context(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+    new_context_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    with_transaction(conn, |conn| {
+        ensure_edge_endpoints_exist(conn, parent_id, child_id)?;
+        if let Some(new_context_id) = new_context_id {
+            ensure_fractal_exists(conn, new_context_id)?;
+        }
+
+        if context_id == new_context_id {
+            return has_child_edge_exists(conn, parent_id, child_id, context_id);
+        }
+
+        // The edge is already there in the new context, drop the old one instead of duplicating
+        if has_child_edge_exists(conn, parent_id, child_id, new_context_id)? {
+            return delete_has_child_edge(conn, parent_id, child_id, context_id);
+        }
+
+        if !has_child_edge_exists(conn, parent_id, child_id, context_id)? {
+            return Ok(false);
+        }
+        ensure_no_cycle(conn, parent_id, child_id, new_context_id)?;
+
+        let query = format!(
+            "
+            MATCH (parent:Fractal {{id: $parent_id}})-[r:HAS_CHILD]->(child:Fractal {{id: $child_id}})
+            WHERE {}
+            SET r.context_id = $new_context_id
+            RETURN count(r) > 0 as updated
+            ",
+            edge_context_condition(context_id)
+        );
+        let mut params = edge_params(parent_id, child_id, context_id);
+        params.push(("new_context_id", optional_uuid(new_context_id)));
+        let mut stmt = conn.prepare(&query)?;
+        let result = conn.execute(&mut stmt, params)?;
+
+        extract_flag(result.into_iter().next(), "Failed to update has_child edge")
+    })
+}
+
+/// Moves `id` from under `from_parent_id` to under `to_parent_id`, keeping the context of the
+/// `HAS_CHILD` edge in `context_id`.
+///
+/// Fails with [`DataError::RelationNotFound`] when there is no such edge, and with
+/// [`DataError::SelfRelation`] or [`DataError::RelationCycle`] when the new parent is the
+/// fractal itself or one of its descendants in that context. Nothing changes on failure.
+pub fn move_fractal(
+    conn: &Connection,
+    id: &Uuid,
+    from_parent_id: &Uuid,
+    to_parent_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Result<Fractal, DataError> {
+    with_transaction(conn, |conn| {
+        ensure_fractals_exist(conn, &[*id, *from_parent_id, *to_parent_id])?;
+
+        if !delete_has_child_edge(conn, from_parent_id, id, context_id)? {
+            return Err(DataError::RelationNotFound {
+                parent_id: *from_parent_id,
+                child_id: *id,
+            });
+        }
+        insert_has_child_edge(conn, to_parent_id, id, context_id)?;
+
+        get_fractal_by_id(conn, id)
+    })
+}
+
+/// `WHERE` condition matching a `HAS_CHILD` edge `r` in exactly `context_id`.
+fn edge_context_condition(context_id: Option<&Uuid>) -> &'static str {
+    match context_id {
+        Some(_) => "r.context_id = $context_id",
+        None => "r.context_id IS NULL",
+    }
+}
+
+fn edge_params(
+    parent_id: &Uuid,
+    child_id: &Uuid,
+    context_id: Option<&Uuid>,
+) -> Vec<(&'static str, Value)> {
+    let mut params = vec![
+        ("parent_id", Value::UUID(*parent_id)),
+        ("child_id", Value::UUID(*child_id)),
+    ];
+    if let Some(context_id) = context_id {
+        params.push(("context_id", Value::UUID(*context_id)));
+    }
+    params
+}
+
+fn optional_uuid(id: Option<&Uuid>) -> Value {
+    match id {
+        Some(id) => Value::UUID(*id),
+        None => Value::Null(LogicalType::UUID),
+    }
+}
+
+fn extract_flag(row: Option<Vec<Value>>, error: &str) -> Result<bool, DataError> {
+    row.and_then(|row| match &row[0] {
+        Value::Bool(b) => Some(*b),
+        _ => None,
+    })
+    .ok_or_else(|| DataError::InvalidData(error.to_string()))
+}
+
+/// Gives `fractal_id` the context `context_id`. Returns `false` without creating anything when
+/// it already has that context.
 pub fn add_has_context_edge(
     conn: &Connection,
     fractal_id: &Uuid,
     context_id: &Uuid,
-) -> Result<(), DataError> {
-    let query = "
-        MATCH (f:Fractal {id: $fractal_id}), (c:Fractal {id: $context_id})
-        CREATE (f)-[:HAS_CONTEXT]->(c)
-    ";
+) -> Result<bool, DataError> {
+    with_transaction(conn, |conn| {
+        insert_has_context_edge(conn, fractal_id, context_id)
+    })
+}
+
+/// Removes the context `context_id` from `fractal_id`. Returns whether it had that context.
+pub fn remove_has_context_edge(
+    conn: &Connection,
+    fractal_id: &Uuid,
+    context_id: &Uuid,
+) -> Result<bool, DataError> {
+    with_transaction(conn, |conn| {
+        ensure_fractal_exists(conn, fractal_id)?;
+        ensure_fractal_exists(conn, context_id)?;
+
+        let query = "
+            MATCH (f:Fractal {id: $fractal_id})-[r:HAS_CONTEXT]->(c:Fractal {id: $context_id})
+            DELETE r
+            RETURN count(r) > 0 as removed
+        ";
+        let params = vec![
+            ("fractal_id", Value::UUID(*fractal_id)),
+            ("context_id", Value::UUID(*context_id)),
+        ];
+        let mut stmt = conn.prepare(query)?;
+        let result = conn.execute(&mut stmt, params)?;
+
+        extract_flag(
+            result.into_iter().next(),
+            "Failed to remove has_context edge",
+        )
+    })
+}
+
+fn insert_has_context_edge(
+    conn: &Connection,
+    fractal_id: &Uuid,
+    context_id: &Uuid,
+) -> Result<bool, DataError> {
+    ensure_fractal_exists(conn, fractal_id)?;
+    ensure_fractal_exists(conn, context_id)?;
+
     let params = vec![
         ("fractal_id", Value::UUID(*fractal_id)),
         ("context_id", Value::UUID(*context_id)),
     ];
+
+    let query = "
+        MATCH (f:Fractal {id: $fractal_id})-[r:HAS_CONTEXT]->(c:Fractal {id: $context_id})
+        RETURN count(r) > 0 as found
+    ";
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params.clone())?;
+    if extract_flag(
+        result.into_iter().next(),
+        "Failed to look up has_context edge",
+    )? {
+        return Ok(false);
+    }
+
+    let query = "
+        MATCH (f:Fractal {id: $fractal_id}), (c:Fractal {id: $context_id})
+        CREATE (f)-[:HAS_CONTEXT]->(c)
+    ";
     let mut stmt = conn.prepare(query)?;
     conn.execute(&mut stmt, params)?;
-    Ok(())
+    Ok(true)
 }
 
+/// The only fractal called `name`, or else the only one with `name` as an alias. Fails with
+/// [`DataError::AmbiguousName`] when several share it, see [`find_fractals_by_name`].
 pub fn get_fractal_by_name(conn: &Connection, name: &str) -> Result<Fractal, DataError> {
+    let fractals = get_fractals_with_name(conn, name)?;
+    let has_exact = fractals.iter().any(|(_, exact)| *exact);
+    let mut matches: Vec<Fractal> = fractals
+        .into_iter()
+        .filter(|(_, exact)| *exact == has_exact)
+        .map(|(f, _)| f)
+        .collect();
+
+    match matches.len() {
+        0 => Err(DataError::FractalNotFound(name.to_string())),
+        1 => Ok(matches.remove(0)),
+        count => Err(DataError::AmbiguousName {
+            name: name.to_string(),
+            count,
+        }),
+    }
+}
+
+pub fn get_fractal_by_id(conn: &Connection, id: &Uuid) -> Result<Fractal, DataError> {
     let query = "
-        MATCH (f:Fractal {name: $name})
+        MATCH (f:Fractal {id: $id})
+        WHERE f.deletedAt IS NULL
         RETURN f.id, f.name, f.createdAt, f.updatedAt
     ";
-    let params = vec![("name", Value::String(name.to_string()))];
+    let params = vec![("id", Value::UUID(*id))];
     let mut stmt = conn.prepare(query)?;
     let result = conn.execute(&mut stmt, params)?;
 
     result
         .into_iter()
         .next()
-        .ok_or_else(|| DataError::FractalNotFound(name.to_string()))
+        .ok_or_else(|| DataError::FractalNotFound(id.to_string()))
         .and_then(|row| row_to_fractal(&row))
 }
 
+/// Looks up several fractals at once. The result only contains the ids that exist, in no
+/// particular order.
+pub fn get_fractals_by_ids(conn: &Connection, ids: &[Uuid]) -> Result<Vec<Fractal>, DataError> {
+    let query = "
+        MATCH (f:Fractal)
+        WHERE f.id IN $ids AND f.deletedAt IS NULL
+        RETURN f.id, f.name, f.createdAt, f.updatedAt
+    ";
+    let params = vec![("ids", uuid_list(ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result.into_iter().map(|row| row_to_fractal(&row)).collect()
+}
+
+/// Applies `update` to the fractal and bumps its `updatedAt`.
+///
+/// When `expected_updated_at` is given the update is rejected with [`DataError::StaleUpdate`]
+/// unless it matches the stored `updatedAt`, so clients cannot overwrite changes they have not
+/// seen. Renaming a fractal to one of its aliases drops that alias. Root keeps its name, since
+/// startup recognises it by id and name.
+pub fn update_fractal(
+    conn: &Connection,
+    id: &Uuid,
+    update: &FractalUpdate,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> Result<Fractal, DataError> {
+    with_transaction(conn, |conn| {
+        let current = get_fractal_by_id(conn, id)?;
+
+        if let Some(expected) = expected_updated_at {
+            if expected != current.updated_at {
+                return Err(DataError::StaleUpdate {
+                    id: *id,
+                    expected,
+                    actual: current.updated_at,
+                });
+            }
+        }
+
+        let name = match &update.name {
+            Some(name) if *name != current.name => {
+                if *id == FRACTAL_ROOT_ID {
+                    return Err(DataError::RootFractalProtected);
+                }
+                ensure_rename_available(conn, id, name)?;
+                // Taking one of its aliases as the name leaves no reason to keep the alias
+                let mut aliases = get_aliases(conn, id)?;
+                if aliases.contains(name) {
+                    aliases.retain(|a| a != name);
+                    set_aliases(conn, id, &aliases)?;
+                }
+                name.clone()
+            }
+            _ => current.name,
+        };
+
+        let query = "
+            MATCH (f:Fractal {id: $id})
+            SET f.name = $name, f.updatedAt = $datetime
+            RETURN f.id, f.name, f.createdAt, f.updatedAt
+        ";
+        let params = vec![
+            ("id", Value::UUID(*id)),
+            ("name", Value::String(name)),
+            (
+                "datetime",
+                Value::Timestamp(OffsetDateTime::from(SystemTime::now())),
+            ),
+        ];
+        let mut stmt = conn.prepare(query)?;
+        let result = conn.execute(&mut stmt, params)?;
+
+        result
+            .into_iter()
+            .next()
+            .ok_or_else(|| DataError::FractalNotFound(id.to_string()))
+            .and_then(|row| row_to_fractal(&row))
+    })
+}
+
 pub fn get_children_of_fractal_with_context(
     conn: &Connection,
     fractal_id: &Uuid,
@@ -246,12 +906,14 @@ pub fn get_children_of_fractal_with_context(
         Some(_) => {
             "
             MATCH (f:Fractal {id: $id})-[:HAS_CHILD {context_id: $context_id}]->(child:Fractal)
+            WHERE child.deletedAt IS NULL
             RETURN child.id, child.name, child.createdAt, child.updatedAt
         "
         }
         None => {
             "
             MATCH (f:Fractal {id: $id})-[:HAS_CHILD]->(child:Fractal)
+            WHERE child.deletedAt IS NULL
             RETURN child.id, child.name, child.createdAt, child.updatedAt
         "
         }
@@ -276,11 +938,27 @@ pub fn get_fractal_relations(
     id: &Uuid,
     relation: &str,
 ) -> Result<Vec<Fractal>, DataError> {
+    // Fractals in the trash are left out
     let query = match relation {
-        "parents" => "MATCH (parent:Fractal)-[:HAS_CHILD]->(f:Fractal {id: $id}) RETURN parent",
-        "children" => "MATCH (f:Fractal {id: $id})-[:HAS_CHILD]->(child:Fractal) RETURN child",
+        "parents" => {
+            "MATCH (parent:Fractal)-[:HAS_CHILD]->(f:Fractal {id: $id})
+            WHERE parent.deletedAt IS NULL
+            RETURN parent"
+        }
+        "children" => {
+            "MATCH (f:Fractal {id: $id})-[:HAS_CHILD]->(child:Fractal)
+            WHERE child.deletedAt IS NULL
+            RETURN child"
+        }
         "contexts" => {
-            "MATCH (f:Fractal {id: $id})-[:HAS_CONTEXT]->(context:Fractal) RETURN context"
+            "MATCH (f:Fractal {id: $id})-[:HAS_CONTEXT]->(context:Fractal)
+            WHERE context.deletedAt IS NULL
+            RETURN context"
+        }
+        "provides_context_for" => {
+            "MATCH (dependent:Fractal)-[:HAS_CONTEXT]->(f:Fractal {id: $id})
+            WHERE dependent.deletedAt IS NULL
+            RETURN dependent"
         }
         _ => {
             return Err(DataError::InvalidData(format!(
@@ -302,22 +980,78 @@ pub fn get_fractal_knowledge_with_context(
     fractal_name: &str,
     context_ids: &[Uuid],
 ) -> Result<Vec<Knowledge>, DataError> {
-    let query = "
-        MATCH (f:Fractal {name: $fractal_name})-[:HAS_KNOWLEDGE]->(k:Knowledge)
-        WHERE ALL(contextId IN $context_ids WHERE (k)-[:IN_CONTEXT]->(:Fractal {id: contextId}))
-        RETURN k.id, k.content
-    ";
-    let context_ids_value = Value::List(
-        LogicalType::List {
-            child_type: Box::new(LogicalType::UUID),
-        },
-        context_ids.iter().map(|&id| Value::UUID(id)).collect(),
+    let fractal = get_fractal_by_name(conn, fractal_name)?;
+    get_fractal_knowledge(conn, &fractal.id, context_ids, None, None)
+}
+
+/// Where a knowledge entry sits in the oldest first order, to list the entries after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnowledgeCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl From<&Knowledge> for KnowledgeCursor {
+    fn from(knowledge: &Knowledge) -> Self {
+        KnowledgeCursor {
+            created_at: knowledge.created_at,
+            id: knowledge.id,
+        }
+    }
+}
+
+/// Returns the knowledge of a fractal that is tagged with every one of `context_ids`, oldest
+/// first, starting after `after` and returning at most `limit`.
+///
+/// Entries added or deleted in between do not shift where the next page starts, since `after`
+/// is a position in the order rather than an offset.
+pub fn get_fractal_knowledge(
+    conn: &Connection,
+    fractal_id: &Uuid,
+    context_ids: &[Uuid],
+    after: Option<&KnowledgeCursor>,
+    limit: Option<usize>,
+) -> Result<Vec<Knowledge>, DataError> {
+    let mut context_ids = context_ids.to_vec();
+    context_ids.sort();
+    context_ids.dedup();
+
+    let after_condition = match after {
+        Some(_) => {
+            "AND (k.createdAt > $after_created_at
+                OR (k.createdAt = $after_created_at AND k.id > $after_id))"
+        }
+        None => "",
+    };
+    let mut query = format!(
+        "
+        MATCH (f:Fractal {{id: $fractal_id}})-[:HAS_KNOWLEDGE]->(k:Knowledge)
+        WHERE k.deletedAt IS NULL AND COUNT {{
+            MATCH (k)-[:IN_CONTEXT]->(c:Fractal)
+            WHERE c.id IN $context_ids
+        }} = $context_count {}
+        RETURN k.id, k.content, k.createdAt, k.updatedAt
+        ORDER BY k.createdAt, k.id
+        ",
+        after_condition
     );
-    let params = vec![
-        ("fractal_name", Value::String(fractal_name.to_string())),
-        ("context_ids", context_ids_value),
+    if let Some(limit) = limit {
+        query.push_str(&format!(" LIMIT {}", limit));
+    }
+
+    let mut params = vec![
+        ("fractal_id", Value::UUID(*fractal_id)),
+        ("context_count", Value::Int64(context_ids.len() as i64)),
+        ("context_ids", uuid_list(&context_ids)),
     ];
-    let mut stmt = conn.prepare(query)?;
+    if let Some(after) = after {
+        params.push((
+            "after_created_at",
+            Value::Timestamp(OffsetDateTime::from(SystemTime::from(after.created_at))),
+        ));
+        params.push(("after_id", Value::UUID(after.id)));
+    }
+    let mut stmt = conn.prepare(&query)?;
     let result = conn.execute(&mut stmt, params)?;
 
     result
@@ -326,10 +1060,63 @@ pub fn get_fractal_knowledge_with_context(
         .collect()
 }
 
+pub fn get_knowledge_by_id(conn: &Connection, id: &Uuid) -> Result<Knowledge, DataError> {
+    let query = "
+        MATCH (k:Knowledge {id: $id})
+        WHERE k.deletedAt IS NULL
+        RETURN k.id, k.content, k.createdAt, k.updatedAt
+    ";
+    let params = vec![("id", Value::UUID(*id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .next()
+        .ok_or_else(|| DataError::KnowledgeNotFound(id.to_string()))
+        .and_then(|row| row_to_knowledge(&row))
+}
+
+/// Returns the fractal that owns the knowledge through its `HAS_KNOWLEDGE` edge.
+pub fn get_knowledge_fractal(conn: &Connection, knowledge_id: &Uuid) -> Result<Fractal, DataError> {
+    let query = "
+        MATCH (f:Fractal)-[:HAS_KNOWLEDGE]->(k:Knowledge {id: $id})
+        RETURN f
+    ";
+    let params = vec![("id", Value::UUID(*knowledge_id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .next()
+        .ok_or_else(|| DataError::KnowledgeNotFound(knowledge_id.to_string()))
+        .and_then(|row| row_to_fractal(&row))
+}
+
+/// Returns the fractals the knowledge is tagged with through `IN_CONTEXT` edges.
+pub fn get_knowledge_contexts(
+    conn: &Connection,
+    knowledge_id: &Uuid,
+) -> Result<Vec<Fractal>, DataError> {
+    let query = "
+        MATCH (k:Knowledge {id: $id})-[:IN_CONTEXT]->(c:Fractal)
+        WHERE c.deletedAt IS NULL
+        RETURN c
+    ";
+    let params = vec![("id", Value::UUID(*knowledge_id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result.into_iter().map(|row| row_to_fractal(&row)).collect()
+}
+
 fn row_to_knowledge(row: &[Value]) -> Result<Knowledge, DataError> {
     Ok(Knowledge {
         id: extract_uuid(&row[0], "id")?,
         content: extract_string(&row[1], "content")?,
+        created_at: extract_datetime(&row[2], "createdAt")?,
+        updated_at: extract_datetime(&row[3], "updatedAt")?,
     })
 }
 
@@ -397,35 +1184,30 @@ fn extract_datetime(value: &Value, field: &str) -> Result<DateTime<Utc>, DataErr
 }
 
 pub fn get_root_fractal(conn: &Connection) -> Result<Fractal, DataError> {
-    get_fractal_by_name(conn, "Root")
-}
-
-pub fn delete_fractal(conn: &Connection, id: &Uuid) -> Result<bool, DataError> {
-    let query = "
-        MATCH (f:Fractal {id: $id})
-        DETACH DELETE f
-        RETURN count(f) > 0 as deleted
-    ";
-    let params = vec![("id", Value::UUID(*id))];
-    let mut stmt = conn.prepare(query)?;
-    let result = conn.execute(&mut stmt, params)?;
-
-    result
-        .into_iter()
-        .next()
-        .and_then(|row| match &row[0] {
-            Value::Bool(b) => Some(*b),
-            _ => None,
-        })
-        .ok_or_else(|| DataError::InvalidData("Failed to delete fractal".to_string()))
+    get_fractal_by_id(conn, &FRACTAL_ROOT_ID)
 }
 
+/// Creates a knowledge entry owned by `fractal_id` and tagged with `context_ids`, atomically.
 pub fn add_knowledge(
     conn: &Connection,
     fractal_id: &Uuid,
     content: &str,
     context_ids: &[Uuid],
 ) -> Result<Knowledge, DataError> {
+    with_transaction(conn, |conn| {
+        let knowledge = insert_knowledge(conn, fractal_id, content)?;
+        insert_in_context_edges(conn, &knowledge.id, context_ids)?;
+        Ok(knowledge)
+    })
+}
+
+fn insert_knowledge(
+    conn: &Connection,
+    fractal_id: &Uuid,
+    content: &str,
+) -> Result<Knowledge, DataError> {
+    ensure_fractal_exists(conn, fractal_id)?;
+
     let query = "
         MATCH (f:Fractal {id: $fractal_id})
         CREATE (k:Knowledge {
@@ -435,29 +1217,17 @@ pub fn add_knowledge(
             updatedAt: $datetime
         })
         CREATE (f)-[:HAS_KNOWLEDGE]->(k)
-        WITH k
-        UNWIND $context_ids AS context_id
-        MATCH (c:Fractal {id: context_id})
-        CREATE (k)-[:IN_CONTEXT]->(c)
-        RETURN k.id, k.content
+        RETURN k.id, k.content, k.createdAt, k.updatedAt
     ";
 
     let system_time = std::time::SystemTime::now();
     let datetime = OffsetDateTime::from(system_time);
 
-    let context_ids_value = Value::List(
-        LogicalType::List {
-            child_type: Box::new(LogicalType::UUID),
-        },
-        context_ids.iter().map(|&id| Value::UUID(id)).collect(),
-    );
-
     let params = vec![
         ("fractal_id", Value::UUID(*fractal_id)),
         ("knowledge_id", Value::UUID(Uuid::new_v4())),
         ("content", Value::String(content.to_string())),
         ("datetime", Value::Timestamp(datetime)),
-        ("context_ids", context_ids_value),
     ];
     let mut stmt = conn.prepare(query)?;
     let result = conn.execute(&mut stmt, params)?;
@@ -468,3 +1238,247 @@ pub fn add_knowledge(
         .ok_or_else(|| DataError::InvalidData("Failed to create knowledge".to_string()))
         .and_then(|row| row_to_knowledge(&row))
 }
+
+fn insert_in_context_edges(
+    conn: &Connection,
+    knowledge_id: &Uuid,
+    context_ids: &[Uuid],
+) -> Result<(), DataError> {
+    if context_ids.is_empty() {
+        return Ok(());
+    }
+    ensure_fractals_exist(conn, context_ids)?;
+
+    let query = "
+        MATCH (k:Knowledge {id: $knowledge_id})
+        UNWIND $context_ids AS context_id
+        MATCH (c:Fractal {id: context_id})
+        CREATE (k)-[:IN_CONTEXT]->(c)
+    ";
+    let mut context_ids = context_ids.to_vec();
+    context_ids.sort();
+    context_ids.dedup();
+
+    let params = vec![
+        ("knowledge_id", Value::UUID(*knowledge_id)),
+        ("context_ids", uuid_list(&context_ids)),
+    ];
+    let mut stmt = conn.prepare(query)?;
+    conn.execute(&mut stmt, params)?;
+    Ok(())
+}
+
+/// Replaces the content of a knowledge entry.
+pub fn update_knowledge(
+    conn: &Connection,
+    id: &Uuid,
+    content: &str,
+) -> Result<Knowledge, DataError> {
+    with_transaction(conn, |conn| {
+        get_knowledge_by_id(conn, id)?;
+
+        let query = "
+            MATCH (k:Knowledge {id: $id})
+            SET k.content = $content
+        ";
+        let params = vec![
+            ("id", Value::UUID(*id)),
+            ("content", Value::String(content.to_string())),
+        ];
+        let mut stmt = conn.prepare(query)?;
+        conn.execute(&mut stmt, params)?;
+
+        touch_knowledge(conn, id)
+    })
+}
+
+/// Replaces the `IN_CONTEXT` edges of a knowledge entry with edges to `context_ids`.
+///
+/// Fails with [`DataError::FractalsNotFound`] listing the contexts that do not exist.
+pub fn set_knowledge_contexts(
+    conn: &Connection,
+    id: &Uuid,
+    context_ids: &[Uuid],
+) -> Result<Knowledge, DataError> {
+    with_transaction(conn, |conn| {
+        get_knowledge_by_id(conn, id)?;
+
+        let query = "
+            MATCH (k:Knowledge {id: $id})-[r:IN_CONTEXT]->(:Fractal)
+            DELETE r
+        ";
+        let params = vec![("id", Value::UUID(*id))];
+        let mut stmt = conn.prepare(query)?;
+        conn.execute(&mut stmt, params)?;
+
+        insert_in_context_edges(conn, id, context_ids)?;
+        touch_knowledge(conn, id)
+    })
+}
+
+/// Moves a knowledge entry to another fractal, keeping its contexts.
+pub fn move_knowledge(
+    conn: &Connection,
+    id: &Uuid,
+    fractal_id: &Uuid,
+) -> Result<Knowledge, DataError> {
+    with_transaction(conn, |conn| {
+        get_knowledge_by_id(conn, id)?;
+        get_fractal_by_id(conn, fractal_id)?;
+
+        let query = "
+            MATCH (:Fractal)-[r:HAS_KNOWLEDGE]->(k:Knowledge {id: $id})
+            DELETE r
+        ";
+        let params = vec![("id", Value::UUID(*id))];
+        let mut stmt = conn.prepare(query)?;
+        conn.execute(&mut stmt, params)?;
+
+        let query = "
+            MATCH (f:Fractal {id: $fractal_id}), (k:Knowledge {id: $id})
+            CREATE (f)-[:HAS_KNOWLEDGE]->(k)
+        ";
+        let params = vec![
+            ("fractal_id", Value::UUID(*fractal_id)),
+            ("id", Value::UUID(*id)),
+        ];
+        let mut stmt = conn.prepare(query)?;
+        conn.execute(&mut stmt, params)?;
+
+        touch_knowledge(conn, id)
+    })
+}
+
+/// Moves a knowledge entry to the trash. Returns `false` when there is no such entry.
+pub fn delete_knowledge(
+    conn: &Connection,
+    id: &Uuid,
+    deleted_by: Option<&str>,
+) -> Result<bool, DataError> {
+    with_transaction(conn, |conn| {
+        match get_knowledge_by_id(conn, id) {
+            Ok(_) => {}
+            Err(DataError::KnowledgeNotFound(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        }
+
+        mark_deleted(conn, "Knowledge", &[*id], &Uuid::new_v4(), deleted_by)?;
+        Ok(true)
+    })
+}
+
+/// Moves the `table` nodes with `ids` to the trash. Nodes deleted by the same operation share
+/// `deletion_id` and are restored together. Their edges are left in place.
+fn mark_deleted(
+    conn: &Connection,
+    table: &str,
+    ids: &[Uuid],
+    deletion_id: &Uuid,
+    deleted_by: Option<&str>,
+) -> Result<(), DataError> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let query = format!(
+        "
+        MATCH (n:{})
+        WHERE n.id IN $ids AND n.deletedAt IS NULL
+        SET n.deletedAt = $datetime, n.deletedBy = $deleted_by, n.deletionId = $deletion_id
+        ",
+        table
+    );
+    let params = vec![
+        ("ids", uuid_list(ids)),
+        (
+            "datetime",
+            Value::Timestamp(OffsetDateTime::from(SystemTime::now())),
+        ),
+        (
+            "deleted_by",
+            match deleted_by {
+                Some(name) => Value::String(name.to_string()),
+                None => Value::Null(LogicalType::String),
+            },
+        ),
+        ("deletion_id", Value::UUID(*deletion_id)),
+    ];
+    let mut stmt = conn.prepare(&query)?;
+    conn.execute(&mut stmt, params)?;
+
+    Ok(())
+}
+
+/// Sets `updatedAt` of a knowledge entry to now and returns the entry.
+fn touch_knowledge(conn: &Connection, id: &Uuid) -> Result<Knowledge, DataError> {
+    let query = "
+        MATCH (k:Knowledge {id: $id})
+        SET k.updatedAt = $datetime
+        RETURN k.id, k.content, k.createdAt, k.updatedAt
+    ";
+    let params = vec![
+        ("id", Value::UUID(*id)),
+        (
+            "datetime",
+            Value::Timestamp(OffsetDateTime::from(SystemTime::now())),
+        ),
+    ];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .next()
+        .ok_or_else(|| DataError::KnowledgeNotFound(id.to_string()))
+        .and_then(|row| row_to_knowledge(&row))
+}
+
+/// Fails with [`DataError::FractalNotFound`] if there is no fractal with `id`.
+fn ensure_fractal_exists(conn: &Connection, id: &Uuid) -> Result<(), DataError> {
+    get_fractal_by_id(conn, id).map(|_| ())
+}
+
+fn ensure_edge_endpoints_exist(
+    conn: &Connection,
+    parent_id: &Uuid,
+    child_id: &Uuid,
+) -> Result<(), DataError> {
+    ensure_fractal_exists(conn, parent_id)?;
+    ensure_fractal_exists(conn, child_id)
+}
+
+/// Fails with [`DataError::FractalsNotFound`] listing every one of `ids` that does not exist.
+fn ensure_fractals_exist(conn: &Connection, ids: &[Uuid]) -> Result<(), DataError> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let found = get_fractals_by_ids(conn, ids)?;
+    let missing: Vec<Uuid> = ids
+        .iter()
+        .filter(|id| !found.iter().any(|f| f.id == **id))
+        .copied()
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(DataError::FractalsNotFound(missing))
+    }
+}
+
+fn format_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(Uuid::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn uuid_list(ids: &[Uuid]) -> Value {
+    Value::List(
+        LogicalType::List {
+            child_type: Box::new(LogicalType::UUID),
+        },
+        ids.iter().map(|&id| Value::UUID(id)).collect(),
+    )
+}
@@ -0,0 +1,290 @@
+use super::{
+    edge_context_condition, extract_flag, extract_string, extract_uuid, get_fractal_by_id,
+    get_paths_from_root, row_to_fractal, with_transaction, DataError, Fractal, FractalPath,
+    ParentLink, PATH_SEPARATOR,
+};
+use kuzu::{Connection, LogicalType, Value};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A fractal with the name that was looked up, together with every way down to it from Root to
+/// tell it apart from fractals with the same name.
+#[derive(Debug, Clone)]
+pub struct NameCandidate {
+    pub fractal: Fractal,
+    pub paths: Vec<FractalPath>,
+    /// The alias that matched, `None` when the fractal is called by the name itself.
+    pub matched_alias: Option<String>,
+}
+
+/// Every fractal called `name` or having it as an alias, outside the trash. Fractals called
+/// `name` come first, each group oldest first.
+///
+/// Names are only unique among the children a parent has in one context, so unlike
+/// `get_fractal_by_name` this does not fail when several fractals share a name.
+pub fn find_fractals_by_name(
+    conn: &Connection,
+    name: &str,
+) -> Result<Vec<NameCandidate>, DataError> {
+    let mut candidates = Vec::new();
+    for (fractal, exact) in get_fractals_with_name(conn, name)? {
+        let paths = get_paths_from_root(conn, &fractal.id, None, None)?;
+        candidates.push(NameCandidate {
+            fractal,
+            paths,
+            matched_alias: (!exact).then(|| name.to_string()),
+        });
+    }
+    Ok(candidates)
+}
+
+/// Live fractals called `name` or having it as an alias, flagged `true` for the former and
+/// ordered like [`find_fractals_by_name`].
+pub(super) fn get_fractals_with_name(
+    conn: &Connection,
+    name: &str,
+) -> Result<Vec<(Fractal, bool)>, DataError> {
+    let query = "
+        MATCH (f:Fractal)
+        WHERE f.deletedAt IS NULL AND (f.name = $name OR list_contains(f.aliases, $name))
+        RETURN f.id, f.name, f.createdAt, f.updatedAt
+        ORDER BY f.createdAt, f.id
+    ";
+    let params = vec![("name", Value::String(name.to_string()))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    let mut fractals = result
+        .into_iter()
+        .map(|row| {
+            let fractal = row_to_fractal(&row)?;
+            let exact = fractal.name == name;
+            Ok((fractal, exact))
+        })
+        .collect::<Result<Vec<_>, DataError>>()?;
+    // Stable, so each group stays oldest first
+    fractals.sort_by_key(|(_, exact)| !exact);
+    Ok(fractals)
+}
+
+/// The other names `id` can be looked up by, in the order they were added.
+pub fn get_aliases(conn: &Connection, id: &Uuid) -> Result<Vec<String>, DataError> {
+    let query = "
+        MATCH (f:Fractal {id: $id})
+        RETURN f.aliases
+    ";
+    let params = vec![("id", Value::UUID(*id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    let row = result
+        .into_iter()
+        .next()
+        .ok_or_else(|| DataError::FractalNotFound(id.to_string()))?;
+    extract_aliases(&row[0])
+}
+
+/// Lets `id` also be looked up as `alias`, in one transaction. Aliases count as names, so this
+/// fails with [`DataError::FractalAlreadyExists`] when a fractal next to `id` is called `alias`
+/// or has it as an alias, and with [`DataError::InvalidAlias`] when `alias` is blank or contains
+/// [`PATH_SEPARATOR`], which would keep it from being used in a path. Adding the fractal's own
+/// name or an alias it has changes nothing.
+pub fn add_alias(conn: &Connection, id: &Uuid, alias: &str) -> Result<Fractal, DataError> {
+    if alias.trim().is_empty() || alias.contains(PATH_SEPARATOR) {
+        return Err(DataError::InvalidAlias(alias.to_string()));
+    }
+
+    with_transaction(conn, |conn| {
+        let fractal = get_fractal_by_id(conn, id)?;
+        let mut aliases = get_aliases(conn, id)?;
+        if fractal.name == alias || aliases.iter().any(|a| a == alias) {
+            return Ok(fractal);
+        }
+
+        ensure_rename_available(conn, id, alias)?;
+        aliases.push(alias.to_string());
+        set_aliases(conn, id, &aliases)
+    })
+}
+
+/// Removes `alias` from `id`, in one transaction. Removing an alias the fractal does not have
+/// changes nothing.
+pub fn remove_alias(conn: &Connection, id: &Uuid, alias: &str) -> Result<Fractal, DataError> {
+    with_transaction(conn, |conn| {
+        let fractal = get_fractal_by_id(conn, id)?;
+        let mut aliases = get_aliases(conn, id)?;
+        let count = aliases.len();
+        aliases.retain(|a| a != alias);
+        if aliases.len() == count {
+            return Ok(fractal);
+        }
+
+        set_aliases(conn, id, &aliases)
+    })
+}
+
+pub(super) fn set_aliases(
+    conn: &Connection,
+    id: &Uuid,
+    aliases: &[String],
+) -> Result<Fractal, DataError> {
+    let query = "
+        MATCH (f:Fractal {id: $id})
+        SET f.aliases = $aliases, f.updatedAt = $datetime
+        RETURN f.id, f.name, f.createdAt, f.updatedAt
+    ";
+    let aliases = aliases.iter().map(|a| Value::String(a.clone())).collect();
+    let params = vec![
+        ("id", Value::UUID(*id)),
+        ("aliases", Value::List(LogicalType::String, aliases)),
+        (
+            "datetime",
+            Value::Timestamp(OffsetDateTime::from(SystemTime::now())),
+        ),
+    ];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .next()
+        .ok_or_else(|| DataError::FractalNotFound(id.to_string()))
+        .and_then(|row| row_to_fractal(&row))
+}
+
+/// Fractals created before aliases existed have `NULL` instead of an empty list.
+fn extract_aliases(value: &Value) -> Result<Vec<String>, DataError> {
+    match value {
+        Value::Null(_) => Ok(vec![]),
+        Value::List(_, aliases) => aliases
+            .iter()
+            .map(|a| extract_string(a, "aliases"))
+            .collect(),
+        _ => Err(DataError::InvalidData(format!(
+            "Expected List for 'aliases', found {:?}",
+            value
+        ))),
+    }
+}
+
+/// Whether `name` is taken where a fractal linked through `parent` would live: among the
+/// children the parent has in that link's context, or among the fractals without a parent when
+/// `parent` is `None`. `except_id` is not counted, so a fractal never collides with itself.
+///
+/// Aliases take a name just like names do. Names stay taken while a fractal is in the trash, so
+/// it can always be restored.
+pub(super) fn is_name_taken(
+    conn: &Connection,
+    name: &str,
+    parent: Option<&ParentLink>,
+    except_id: Option<&Uuid>,
+) -> Result<bool, DataError> {
+    let (pattern, scope) = match parent {
+        Some(link) => (
+            "MATCH (p:Fractal {id: $parent_id})-[r:HAS_CHILD]->(f:Fractal)",
+            edge_context_condition(link.context_id.as_ref()),
+        ),
+        None => (
+            "MATCH (f:Fractal)",
+            "NOT EXISTS { MATCH (:Fractal)-[:HAS_CHILD]->(f) }",
+        ),
+    };
+    let except = match except_id {
+        Some(_) => "AND f.id <> $except_id",
+        None => "",
+    };
+    let query = format!(
+        "
+        {}
+        WHERE (f.name = $name OR list_contains(f.aliases, $name)) AND {} {}
+        RETURN count(f) > 0 AS taken
+        ",
+        pattern, scope, except
+    );
+
+    let mut params = vec![("name", Value::String(name.to_string()))];
+    if let Some(link) = parent {
+        params.push(("parent_id", Value::UUID(link.parent_id)));
+        if let Some(context_id) = link.context_id {
+            params.push(("context_id", Value::UUID(context_id)));
+        }
+    }
+    if let Some(except_id) = except_id {
+        params.push(("except_id", Value::UUID(*except_id)));
+    }
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    extract_flag(result.into_iter().next(), "Failed to look up fractal name")
+}
+
+/// Fails with [`DataError::FractalAlreadyExists`] when [`is_name_taken`].
+pub(super) fn ensure_name_available(
+    conn: &Connection,
+    name: &str,
+    parent: Option<&ParentLink>,
+    except_id: Option<&Uuid>,
+) -> Result<(), DataError> {
+    if is_name_taken(conn, name, parent, except_id)? {
+        Err(DataError::FractalAlreadyExists(name.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that fractal `id` can be called `name` under every one of its parents, or among the
+/// fractals without a parent when it has none.
+pub(super) fn ensure_rename_available(
+    conn: &Connection,
+    id: &Uuid,
+    name: &str,
+) -> Result<(), DataError> {
+    let parents = get_parent_links(conn, id)?;
+    if parents.is_empty() {
+        return ensure_name_available(conn, name, None, Some(id));
+    }
+    for link in &parents {
+        ensure_name_available(conn, name, Some(link), Some(id))?;
+    }
+    Ok(())
+}
+
+/// Checks that neither the name nor any alias of `id` is taken under `parent`.
+pub(super) fn ensure_names_available_under(
+    conn: &Connection,
+    id: &Uuid,
+    parent: &ParentLink,
+) -> Result<(), DataError> {
+    let fractal = get_fractal_by_id(conn, id)?;
+    ensure_name_available(conn, &fractal.name, Some(parent), Some(id))?;
+    for alias in get_aliases(conn, id)? {
+        ensure_name_available(conn, &alias, Some(parent), Some(id))?;
+    }
+    Ok(())
+}
+
+/// The `HAS_CHILD` edges leading to `id`, including the ones from parents in the trash.
+fn get_parent_links(conn: &Connection, id: &Uuid) -> Result<Vec<ParentLink>, DataError> {
+    let query = "
+        MATCH (p:Fractal)-[r:HAS_CHILD]->(f:Fractal {id: $id})
+        RETURN p.id, r.context_id
+    ";
+    let params = vec![("id", Value::UUID(*id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let context_id = match &row[1] {
+                Value::Null(_) => None,
+                value => Some(extract_uuid(value, "context_id")?),
+            };
+            Ok(ParentLink {
+                parent_id: extract_uuid(&row[0], "id")?,
+                context_id,
+            })
+        })
+        .collect()
+}
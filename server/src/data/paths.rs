@@ -0,0 +1,377 @@
+use super::{
+    extract_uuid, get_fractal_by_id, get_fractals_by_ids, row_to_fractal, uuid_list, DataError,
+    Fractal, GraphEdge, RelationType, FRACTAL_ROOT_ID, MAX_HIERARCHY_DEPTH,
+};
+use kuzu::{Connection, InternalID, NodeVal, RelVal, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Longest path [`find_paths`] looks for.
+pub const MAX_PATH_LENGTH: usize = 10;
+
+/// Most paths [`get_paths_from_root`] returns.
+pub const MAX_ROOT_PATHS: usize = 100;
+
+/// Separates the names in a path like `Programming/Rust/String`.
+pub const PATH_SEPARATOR: char = '/';
+
+/// A way to get from one fractal to another.
+#[derive(Debug, Clone)]
+pub struct FractalPath {
+    /// Every fractal on the path, from the start to the end.
+    pub fractals: Vec<Fractal>,
+    /// `edges[i]` connects `fractals[i]` and `fractals[i + 1]`, in whichever direction it is
+    /// stored.
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Up to `k` shortest paths between two fractals, shortest first, with at most `max_length` edges
+/// capped at [`MAX_PATH_LENGTH`]. Paths of the same length are ordered by the ids of the fractals
+/// they pass through.
+///
+/// Edges of `relation_types` are followed both ways, so siblings are connected through their
+/// parent. `HAS_KNOWLEDGE` is ignored. A path never visits a fractal twice or passes through the
+/// trash.
+pub fn find_paths(
+    conn: &Connection,
+    from_id: &Uuid,
+    to_id: &Uuid,
+    max_length: usize,
+    relation_types: &[RelationType],
+    k: usize,
+) -> Result<Vec<FractalPath>, DataError> {
+    let from = get_fractal_by_id(conn, from_id)?;
+    get_fractal_by_id(conn, to_id)?;
+
+    if k == 0 {
+        return Ok(vec![]);
+    }
+    if from_id == to_id {
+        return Ok(vec![FractalPath {
+            fractals: vec![from],
+            edges: vec![],
+        }]);
+    }
+
+    let labels: Vec<&str> = relation_types
+        .iter()
+        .filter_map(|relation_type| match relation_type {
+            RelationType::HasChild => Some("HAS_CHILD"),
+            RelationType::HasContext => Some("HAS_CONTEXT"),
+            RelationType::HasKnowledge => None,
+        })
+        .collect();
+    let max_length = max_length.min(MAX_PATH_LENGTH);
+    if labels.is_empty() || max_length == 0 {
+        return Ok(vec![]);
+    }
+
+    let params = vec![
+        ("from_id", Value::UUID(*from_id)),
+        ("to_id", Value::UUID(*to_id)),
+    ];
+
+    // One length at a time, so nothing longer than the k-th shortest path is ever enumerated
+    let mut paths = Vec::new();
+    for length in 1..=max_length {
+        let query = format!(
+            "
+            MATCH (a:Fractal {{id: $from_id}})
+                -[e:{}*{}..{} (r, n | WHERE n.deletedAt IS NULL)]-
+                (b:Fractal {{id: $to_id}})
+            RETURN id(a), id(b), e
+            ORDER BY CAST(properties(nodes(e), 'id') AS STRING)
+            LIMIT {}
+            ",
+            labels.join("|"),
+            length,
+            length,
+            k - paths.len()
+        );
+        paths.extend(query_paths(conn, &query, params.clone(), from_id, to_id)?);
+        if paths.len() >= k {
+            break;
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Every way down `HAS_CHILD` edges from Root to `id`, at most [`MAX_ROOT_PATHS`] of them and
+/// none longer than [`MAX_HIERARCHY_DEPTH`], shortest first and then by the names along the way.
+///
+/// Like in [`resolve_path`], an edge with a context is only followed when that context is Root
+/// or appears earlier in the path, so every path listed can be resolved again. When `context_id`
+/// is given the paths are the ones that apply in that context: edges in exactly `context_id` are
+/// followed too, wherever it is. When `through_id` is given only paths passing through that
+/// fractal are kept, e.g. the ones of "String" through "Rust".
+pub fn get_paths_from_root(
+    conn: &Connection,
+    id: &Uuid,
+    context_id: Option<&Uuid>,
+    through_id: Option<&Uuid>,
+) -> Result<Vec<FractalPath>, DataError> {
+    let fractal = get_fractal_by_id(conn, id)?;
+    if *id == FRACTAL_ROOT_ID {
+        return Ok(vec![FractalPath {
+            fractals: vec![fractal],
+            edges: vec![],
+        }]);
+    }
+
+    let in_context = match context_id {
+        Some(_) => "OR contexts[i] = $context_id",
+        None => "",
+    };
+    let through = match through_id {
+        Some(_) => {
+            "
+            AND ($through_id IN [a.id, b.id]
+                OR list_contains(properties(nodes(e), 'id'), $through_id))
+            "
+        }
+        None => "",
+    };
+    // `walked[i]` is the parent of the `i`-th edge, so its context has to come at or before it
+    let query = format!(
+        "
+        MATCH (a:Fractal {{id: $from_id}})
+            -[e:HAS_CHILD*1..{} (r, n | WHERE n.deletedAt IS NULL)]->
+            (b:Fractal {{id: $to_id}})
+        WITH a, b, e,
+            properties(rels(e), 'context_id') AS contexts,
+            list_concat([a.id], properties(nodes(e), 'id')) AS walked
+        WHERE all(i IN range(1, size(contexts)) WHERE contexts[i] IS NULL
+                OR (list_position(walked, contexts[i]) >= 1
+                    AND list_position(walked, contexts[i]) <= i)
+                {})
+            {}
+        RETURN id(a), id(b), e
+        ORDER BY length(e)
+        LIMIT {}
+        ",
+        MAX_HIERARCHY_DEPTH, in_context, through, MAX_ROOT_PATHS
+    );
+    let mut params = vec![
+        ("from_id", Value::UUID(FRACTAL_ROOT_ID)),
+        ("to_id", Value::UUID(*id)),
+    ];
+    if let Some(context_id) = context_id {
+        params.push(("context_id", Value::UUID(*context_id)));
+    }
+    if let Some(through_id) = through_id {
+        params.push(("through_id", Value::UUID(*through_id)));
+    }
+
+    let mut paths = query_paths(conn, &query, params, &FRACTAL_ROOT_ID, id)?;
+    paths.sort_by_cached_key(|p| {
+        let names: Vec<String> = p.fractals.iter().map(|f| f.name.clone()).collect();
+        (p.edges.len(), names)
+    });
+    Ok(paths)
+}
+
+/// Resolves a path like `Programming/Rust/String` by following `HAS_CHILD` edges from Root, one
+/// name or alias at a time. Leading and trailing separators are ignored and an empty path is
+/// Root itself.
+///
+/// An edge with a context is only followed when that context is Root or appears earlier in the
+/// path, so `Programming/Python/String/.count()` resolves but `Programming/String/.count()` does
+/// not. When same-named children match, the one linked in the context closest to it is taken.
+/// The returned path starts at Root.
+pub fn resolve_path(conn: &Connection, path: &str) -> Result<FractalPath, DataError> {
+    let mut resolved = FractalPath {
+        fractals: vec![get_fractal_by_id(conn, &FRACTAL_ROOT_ID)?],
+        edges: vec![],
+    };
+
+    let trimmed = path.trim_matches(PATH_SEPARATOR);
+    if trimmed.is_empty() {
+        return Ok(resolved);
+    }
+
+    for segment in trimmed.split(PATH_SEPARATOR) {
+        let parent = &resolved.fractals[resolved.fractals.len() - 1];
+        let walked: Vec<Uuid> = resolved.fractals.iter().map(|f| f.id).collect();
+        let (child, context_id) = get_child_on_path(conn, &parent.id, segment, &walked)?
+            .ok_or_else(|| DataError::PathSegmentNotFound {
+                path: path.to_string(),
+                segment: segment.to_string(),
+                parent: parent.name.clone(),
+            })?;
+
+        resolved.edges.push(GraphEdge {
+            relation_type: RelationType::HasChild,
+            source_id: parent.id,
+            target_id: child.id,
+            context_id,
+        });
+        resolved.fractals.push(child);
+    }
+
+    Ok(resolved)
+}
+
+/// The child of `parent_id` called `name`, or having it as an alias, linked without a context or
+/// in one of `context_ids`, together with the context of the link.
+///
+/// Names are unique per parent and context, so several children can only match through links in
+/// different contexts. The one in the context latest in `context_ids` wins, i.e. the closest.
+fn get_child_on_path(
+    conn: &Connection,
+    parent_id: &Uuid,
+    name: &str,
+    context_ids: &[Uuid],
+) -> Result<Option<(Fractal, Option<Uuid>)>, DataError> {
+    let query = "
+        MATCH (p:Fractal {id: $parent_id})-[r:HAS_CHILD]->(c:Fractal)
+        WHERE (c.name = $name OR list_contains(c.aliases, $name))
+            AND c.deletedAt IS NULL
+            AND (r.context_id IS NULL OR r.context_id IN $context_ids)
+        RETURN c.id, c.name, c.createdAt, c.updatedAt, r.context_id
+    ";
+    let params = vec![
+        ("parent_id", Value::UUID(*parent_id)),
+        ("name", Value::String(name.to_string())),
+        ("context_ids", uuid_list(context_ids)),
+    ];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    let mut best: Option<(usize, Fractal, Option<Uuid>)> = None;
+    for row in result {
+        let context_id = match &row[4] {
+            Value::Null(_) => None,
+            value => Some(extract_uuid(value, "context_id")?),
+        };
+        let rank = context_id
+            .and_then(|id| context_ids.iter().position(|c| *c == id))
+            .map_or(0, |position| position + 1);
+        if !matches!(&best, Some((best_rank, _, _)) if *best_rank >= rank) {
+            best = Some((rank, row_to_fractal(&row)?, context_id));
+        }
+    }
+
+    Ok(best.map(|(_, fractal, context_id)| (fractal, context_id)))
+}
+
+/// Runs a query returning `id(a), id(b), e` for paths from `from_id` to `to_id` and loads the
+/// fractals along them.
+fn query_paths(
+    conn: &Connection,
+    query: &str,
+    params: Vec<(&str, Value)>,
+    from_id: &Uuid,
+    to_id: &Uuid,
+) -> Result<Vec<FractalPath>, DataError> {
+    // Kuzu walks may repeat nodes by default, which makes for useless detours
+    conn.query("CALL recursive_pattern_semantic='ACYCLIC'")?;
+    let result = conn
+        .prepare(query)
+        .and_then(|mut stmt| conn.execute(&mut stmt, params));
+    conn.query("CALL recursive_pattern_semantic='WALK'")?;
+
+    let mut paths = Vec::new();
+    for row in result? {
+        let (Value::InternalID(a), Value::InternalID(b), Value::RecursiveRel { nodes, rels }) =
+            (&row[0], &row[1], &row[2])
+        else {
+            return Err(DataError::InvalidData(format!(
+                "Expected a path, found {:?}",
+                row
+            )));
+        };
+        paths.push(decode_path((a, from_id), (b, to_id), nodes, rels)?);
+    }
+
+    let ids: Vec<Uuid> = paths.iter().flat_map(|(ids, _)| ids.clone()).collect();
+    let fractals: HashMap<Uuid, Fractal> = get_fractals_by_ids(conn, &ids)?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+
+    paths
+        .into_iter()
+        .map(|(ids, edges)| {
+            let fractals = ids
+                .iter()
+                .map(|id| {
+                    fractals
+                        .get(id)
+                        .cloned()
+                        .ok_or_else(|| DataError::FractalNotFound(id.to_string()))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(FractalPath { fractals, edges })
+        })
+        .collect()
+}
+
+/// Follows `rels` from `from` to `to`, returning the ids of the fractals passed and the edges.
+fn decode_path(
+    from: (&InternalID, &Uuid),
+    to: (&InternalID, &Uuid),
+    nodes: &[NodeVal],
+    rels: &[RelVal],
+) -> Result<(Vec<Uuid>, Vec<GraphEdge>), DataError> {
+    let key = |id: &InternalID| (id.table_id, id.offset);
+
+    let mut uuids = HashMap::from([(key(from.0), *from.1), (key(to.0), *to.1)]);
+    for node in nodes {
+        let id = node
+            .get_properties()
+            .iter()
+            .find(|(name, _)| name == "id")
+            .ok_or_else(|| DataError::InvalidData("Path node without an id".to_string()))?;
+        uuids.insert(key(node.get_node_id()), extract_uuid(&id.1, "id")?);
+    }
+    let uuid_of = |id: &InternalID| {
+        uuids
+            .get(&key(id))
+            .copied()
+            .ok_or_else(|| DataError::InvalidData(format!("Unknown node {} on path", id)))
+    };
+
+    let mut ids = vec![*from.1];
+    let mut edges = Vec::new();
+    for rel in rels {
+        let source_id = uuid_of(rel.get_src_node())?;
+        let target_id = uuid_of(rel.get_dst_node())?;
+        let current = ids[ids.len() - 1];
+        let next = if source_id == current {
+            target_id
+        } else if target_id == current {
+            source_id
+        } else {
+            return Err(DataError::InvalidData(format!(
+                "Edge {} -> {} does not continue the path at {}",
+                source_id, target_id, current
+            )));
+        };
+
+        let relation_type = match rel.get_label_name().as_str() {
+            "HAS_CHILD" => RelationType::HasChild,
+            "HAS_CONTEXT" => RelationType::HasContext,
+            other => {
+                return Err(DataError::InvalidData(format!(
+                    "Unexpected edge {} on path",
+                    other
+                )))
+            }
+        };
+        let context_id = match rel.get_properties().iter().find(|(n, _)| n == "context_id") {
+            None | Some((_, Value::Null(_))) => None,
+            Some((_, value)) => Some(extract_uuid(value, "context_id")?),
+        };
+
+        ids.push(next);
+        edges.push(GraphEdge {
+            relation_type,
+            source_id,
+            target_id,
+            context_id,
+        });
+    }
+
+    Ok((ids, edges))
+}
@@ -0,0 +1,233 @@
+use super::{extract_uuid, get_fractal_by_name, row_to_knowledge, uuid_list, DataError, Knowledge};
+use kuzu::{Connection, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How far up the `HAS_CHILD` hierarchy a requested context looks for ancestor contexts.
+pub const MAX_CONTEXT_ANCESTOR_DEPTH: usize = 8;
+
+/// Credit given to a knowledge context that is an ancestor of a requested context, per level of
+/// distance. A parent counts half, a grandparent a quarter and so on.
+pub const ANCESTOR_CONTEXT_DECAY: f64 = 0.5;
+
+#[derive(Debug, Clone)]
+pub struct RankedKnowledge {
+    pub knowledge: Knowledge,
+    /// Share of the requested contexts matched by the knowledge, between 0 and 1.
+    pub score: f64,
+    /// Requested contexts the knowledge matched, exactly or through an ancestor.
+    pub matched_context_ids: Vec<Uuid>,
+}
+
+/// Ranks the knowledge of the named fractal by how well it matches the requested contexts.
+pub fn rank_fractal_knowledge_by_name(
+    conn: &Connection,
+    fractal_name: &str,
+    context_ids: &[Uuid],
+    limit: Option<usize>,
+) -> Result<Vec<RankedKnowledge>, DataError> {
+    let fractal = get_fractal_by_name(conn, fractal_name)?;
+    rank_fractal_knowledge(conn, &fractal.id, context_ids, limit)
+}
+
+/// Ranks the knowledge of a fractal by how well its `IN_CONTEXT` tags match `context_ids`.
+///
+/// Each requested context is worth one point: the full point when the knowledge is tagged with
+/// it, or [`ANCESTOR_CONTEXT_DECAY`] per level when tagged with one of its ancestors. A tag only
+/// counts towards the requested context it matches best. The score is the share of points
+/// earned, knowledge without any points is left out. Equal scores prefer knowledge tagged with
+/// the more specific of the requested contexts, then the older entry.
+///
+/// Without requested contexts all knowledge is returned with a score of 1, oldest first.
+pub fn rank_fractal_knowledge(
+    conn: &Connection,
+    fractal_id: &Uuid,
+    context_ids: &[Uuid],
+    limit: Option<usize>,
+) -> Result<Vec<RankedKnowledge>, DataError> {
+    let mut requested = Vec::with_capacity(context_ids.len());
+    for id in context_ids {
+        if !requested.contains(id) {
+            requested.push(*id);
+        }
+    }
+
+    let knowledge = get_knowledge_with_context_ids(conn, fractal_id)?;
+    let ancestors = get_context_ancestors(conn, &requested)?;
+
+    // How many of the other requested contexts sit above each requested context
+    let specificity: HashMap<Uuid, usize> = requested
+        .iter()
+        .map(|r| {
+            let depth = requested
+                .iter()
+                .filter(|other| ancestors.get(r).is_some_and(|a| a.contains_key(other)))
+                .count();
+            (*r, depth)
+        })
+        .collect();
+
+    let match_weight = |tag: &Uuid, requested: &Uuid| -> f64 {
+        if tag == requested {
+            1.0
+        } else {
+            ancestors
+                .get(requested)
+                .and_then(|a| a.get(tag))
+                .map_or(0.0, |distance| {
+                    ANCESTOR_CONTEXT_DECAY.powi(*distance as i32)
+                })
+        }
+    };
+
+    let mut ranked = Vec::new();
+    for (knowledge, tags) in knowledge {
+        if requested.is_empty() {
+            ranked.push((
+                RankedKnowledge {
+                    knowledge,
+                    score: 1.0,
+                    matched_context_ids: vec![],
+                },
+                0,
+            ));
+            continue;
+        }
+
+        let mut weights: HashMap<Uuid, f64> = HashMap::new();
+        for tag in &tags {
+            let best = requested.iter().map(|r| (r, match_weight(tag, r))).fold(
+                None,
+                |best: Option<(&Uuid, f64)>, (r, w)| match best {
+                    Some((_, best_w)) if best_w >= w => best,
+                    _ => Some((r, w)),
+                },
+            );
+
+            if let Some((r, w)) = best.filter(|(_, w)| *w > 0.0) {
+                let entry = weights.entry(*r).or_insert(0.0);
+                *entry = entry.max(w);
+            }
+        }
+
+        if weights.is_empty() {
+            continue;
+        }
+
+        let score = weights.values().sum::<f64>() / requested.len() as f64;
+        let matched_context_ids: Vec<Uuid> = requested
+            .iter()
+            .filter(|r| weights.contains_key(r))
+            .copied()
+            .collect();
+        let knowledge_specificity = matched_context_ids
+            .iter()
+            .filter(|r| tags.contains(r))
+            .map(|r| specificity[r])
+            .sum::<usize>();
+
+        ranked.push((
+            RankedKnowledge {
+                knowledge,
+                score,
+                matched_context_ids,
+            },
+            knowledge_specificity,
+        ));
+    }
+
+    ranked.sort_by(|(a, a_specificity), (b, b_specificity)| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b_specificity.cmp(a_specificity))
+            .then(a.knowledge.created_at.cmp(&b.knowledge.created_at))
+            .then(a.knowledge.id.cmp(&b.knowledge.id))
+    });
+
+    let ranked = ranked.into_iter().map(|(r, _)| r);
+    Ok(match limit {
+        Some(limit) => ranked.take(limit).collect(),
+        None => ranked.collect(),
+    })
+}
+
+/// Returns every knowledge entry of the fractal together with its `IN_CONTEXT` ids.
+pub(super) fn get_knowledge_with_context_ids(
+    conn: &Connection,
+    fractal_id: &Uuid,
+) -> Result<Vec<(Knowledge, Vec<Uuid>)>, DataError> {
+    let query = "
+        MATCH (f:Fractal {id: $fractal_id})-[:HAS_KNOWLEDGE]->(k:Knowledge)
+        WHERE k.deletedAt IS NULL
+        OPTIONAL MATCH (k)-[:IN_CONTEXT]->(c:Fractal)
+        WHERE c.deletedAt IS NULL
+        RETURN k.id, k.content, k.createdAt, k.updatedAt, collect(c.id)
+    ";
+    let params = vec![("fractal_id", Value::UUID(*fractal_id))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let knowledge = row_to_knowledge(&row)?;
+            let context_ids = match &row[4] {
+                Value::List(_, ids) => ids
+                    .iter()
+                    .filter(|id| !matches!(id, Value::Null(_)))
+                    .map(|id| extract_uuid(id, "contextId"))
+                    .collect::<Result<Vec<_>, _>>()?,
+                other => {
+                    return Err(DataError::InvalidData(format!(
+                        "Expected list of context ids, found {:?}",
+                        other
+                    )))
+                }
+            };
+            Ok((knowledge, context_ids))
+        })
+        .collect()
+}
+
+/// Maps each of `context_ids` to its `HAS_CHILD` ancestors and their shortest distance.
+fn get_context_ancestors(
+    conn: &Connection,
+    context_ids: &[Uuid],
+) -> Result<HashMap<Uuid, HashMap<Uuid, usize>>, DataError> {
+    if context_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let query = format!(
+        "
+        MATCH (a:Fractal)-[e:HAS_CHILD*1..{}]->(c:Fractal)
+        WHERE c.id IN $context_ids
+        RETURN c.id, a.id, min(length(e))
+        ",
+        MAX_CONTEXT_ANCESTOR_DEPTH
+    );
+    let params = vec![("context_ids", uuid_list(context_ids))];
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    let mut ancestors: HashMap<Uuid, HashMap<Uuid, usize>> = HashMap::new();
+    for row in result {
+        let context_id = extract_uuid(&row[0], "contextId")?;
+        let ancestor_id = extract_uuid(&row[1], "ancestorId")?;
+        let distance = match &row[2] {
+            Value::Int64(distance) => *distance as usize,
+            other => {
+                return Err(DataError::InvalidData(format!(
+                    "Expected distance, found {:?}",
+                    other
+                )))
+            }
+        };
+        ancestors
+            .entry(context_id)
+            .or_default()
+            .insert(ancestor_id, distance);
+    }
+
+    Ok(ancestors)
+}
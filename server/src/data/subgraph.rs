@@ -0,0 +1,213 @@
+use super::{
+    extract_uuid, get_fractal_by_id, get_fractals_by_ids, row_to_knowledge, uuid_list, DataError,
+    Fractal, Knowledge, MAX_HIERARCHY_DEPTH,
+};
+use kuzu::{Connection, Value};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// The kinds of edges a [`Subgraph`] can follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelationType {
+    /// `HAS_CHILD` from a parent to a child fractal.
+    HasChild,
+    /// `HAS_CONTEXT` from a fractal to one of its contexts.
+    HasContext,
+    /// `HAS_KNOWLEDGE` from a fractal to a knowledge entry it owns.
+    HasKnowledge,
+}
+
+impl RelationType {
+    pub const ALL: [RelationType; 3] = [
+        RelationType::HasChild,
+        RelationType::HasContext,
+        RelationType::HasKnowledge,
+    ];
+}
+
+/// An edge of a [`Subgraph`]. Only `HAS_CHILD` edges can have a `context_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphEdge {
+    pub relation_type: RelationType,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub context_id: Option<Uuid>,
+}
+
+/// Most fractals and knowledge entries a [`Subgraph`] holds together.
+pub const MAX_SUBGRAPH_NODES: usize = 1000;
+
+/// Fractals and knowledge around a root with the edges between them, each listed once.
+#[derive(Debug, Clone, Default)]
+pub struct Subgraph {
+    /// The root first, then in the order they were reached.
+    pub fractals: Vec<Fractal>,
+    pub knowledge: Vec<Knowledge>,
+    pub edges: Vec<GraphEdge>,
+    /// Whether nodes within reach were left out to stay within the node limit.
+    pub truncated: bool,
+}
+
+/// Collects everything reachable from `root_id` in at most `depth` steps along outgoing edges of
+/// `relation_types`, capped at [`MAX_HIERARCHY_DEPTH`].
+///
+/// When `context_id` is given only `HAS_CHILD` edges in exactly that context are followed, like
+/// `children`. Knowledge entries end a path, fractals and knowledge in the trash are left out.
+/// At most `max_nodes` fractals and knowledge entries, capped at [`MAX_SUBGRAPH_NODES`], are
+/// collected, nearest first; edges to the ones left out are dropped and the subgraph is marked
+/// as truncated.
+pub fn get_subgraph(
+    conn: &Connection,
+    root_id: &Uuid,
+    depth: usize,
+    relation_types: &[RelationType],
+    context_id: Option<&Uuid>,
+    max_nodes: usize,
+) -> Result<Subgraph, DataError> {
+    let root = get_fractal_by_id(conn, root_id)?;
+    let max_nodes = max_nodes.clamp(1, MAX_SUBGRAPH_NODES);
+
+    let mut fractal_ids = vec![root.id];
+    let mut seen_fractals = HashSet::from([root.id]);
+    let mut seen_edges = HashSet::new();
+    let mut subgraph = Subgraph::default();
+
+    let mut frontier = vec![root.id];
+    for _ in 0..depth.min(MAX_HIERARCHY_DEPTH) {
+        if frontier.is_empty() || subgraph.truncated {
+            break;
+        }
+
+        let mut next = Vec::new();
+        for relation_type in RelationType::ALL {
+            if !relation_types.contains(&relation_type) {
+                continue;
+            }
+
+            if relation_type == RelationType::HasKnowledge {
+                for (edge, knowledge) in get_knowledge_edges(conn, &frontier)? {
+                    if fractal_ids.len() + subgraph.knowledge.len() >= max_nodes {
+                        subgraph.truncated = true;
+                        break;
+                    }
+                    subgraph.edges.push(edge);
+                    subgraph.knowledge.push(knowledge);
+                }
+                continue;
+            }
+
+            for edge in get_fractal_edges(conn, &frontier, relation_type, context_id)? {
+                if seen_edges.contains(&edge) {
+                    continue;
+                }
+                if !seen_fractals.contains(&edge.target_id) {
+                    if fractal_ids.len() + subgraph.knowledge.len() >= max_nodes {
+                        subgraph.truncated = true;
+                        continue;
+                    }
+                    seen_fractals.insert(edge.target_id);
+                    fractal_ids.push(edge.target_id);
+                    next.push(edge.target_id);
+                }
+                seen_edges.insert(edge);
+                subgraph.edges.push(edge);
+            }
+        }
+        frontier = next;
+    }
+
+    let mut fractals: HashMap<Uuid, Fractal> = get_fractals_by_ids(conn, &fractal_ids)?
+        .into_iter()
+        .map(|f| (f.id, f))
+        .collect();
+    subgraph.fractals = fractal_ids
+        .iter()
+        .filter_map(|id| fractals.remove(id))
+        .collect();
+
+    Ok(subgraph)
+}
+
+/// Outgoing `HAS_CHILD` or `HAS_CONTEXT` edges of `ids` to fractals outside the trash.
+fn get_fractal_edges(
+    conn: &Connection,
+    ids: &[Uuid],
+    relation_type: RelationType,
+    context_id: Option<&Uuid>,
+) -> Result<Vec<GraphEdge>, DataError> {
+    let query = match (relation_type, context_id) {
+        (RelationType::HasChild, Some(_)) => {
+            "
+            MATCH (f:Fractal)-[r:HAS_CHILD]->(t:Fractal)
+            WHERE f.id IN $ids AND t.deletedAt IS NULL AND r.context_id = $context_id
+            RETURN f.id, t.id, r.context_id
+        "
+        }
+        (RelationType::HasChild, None) => {
+            "
+            MATCH (f:Fractal)-[r:HAS_CHILD]->(t:Fractal)
+            WHERE f.id IN $ids AND t.deletedAt IS NULL
+            RETURN f.id, t.id, r.context_id
+        "
+        }
+        _ => {
+            "
+            MATCH (f:Fractal)-[:HAS_CONTEXT]->(t:Fractal)
+            WHERE f.id IN $ids AND t.deletedAt IS NULL
+            RETURN f.id, t.id, NULL
+        "
+        }
+    };
+    let mut params = vec![("ids", uuid_list(ids))];
+    if let (RelationType::HasChild, Some(context_id)) = (relation_type, context_id) {
+        params.push(("context_id", Value::UUID(*context_id)));
+    }
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let context_id = match &row[2] {
+                Value::Null(_) => None,
+                value => Some(extract_uuid(value, "context_id")?),
+            };
+            Ok(GraphEdge {
+                relation_type,
+                source_id: extract_uuid(&row[0], "id")?,
+                target_id: extract_uuid(&row[1], "id")?,
+                context_id,
+            })
+        })
+        .collect()
+}
+
+/// `HAS_KNOWLEDGE` edges of `ids` together with the knowledge they lead to.
+fn get_knowledge_edges(
+    conn: &Connection,
+    ids: &[Uuid],
+) -> Result<Vec<(GraphEdge, Knowledge)>, DataError> {
+    let query = "
+        MATCH (f:Fractal)-[:HAS_KNOWLEDGE]->(k:Knowledge)
+        WHERE f.id IN $ids AND k.deletedAt IS NULL
+        RETURN k.id, k.content, k.createdAt, k.updatedAt, f.id
+        ORDER BY k.createdAt, k.id
+    ";
+    let params = vec![("ids", uuid_list(ids))];
+    let mut stmt = conn.prepare(query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .map(|row| {
+            let knowledge = row_to_knowledge(&row)?;
+            let edge = GraphEdge {
+                relation_type: RelationType::HasKnowledge,
+                source_id: extract_uuid(&row[4], "id")?,
+                target_id: knowledge.id,
+                context_id: None,
+            };
+            Ok((edge, knowledge))
+        })
+        .collect()
+}
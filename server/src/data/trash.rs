@@ -0,0 +1,262 @@
+use super::{
+    extract_datetime, extract_string, extract_uuid, get_fractal_by_id, get_knowledge_by_id,
+    row_to_fractal, row_to_knowledge, with_transaction, DataError, Fractal, Knowledge,
+};
+use chrono::{DateTime, Utc};
+use kuzu::{Connection, Value};
+use std::time::SystemTime;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Something in the trash.
+#[derive(Debug, Clone)]
+pub enum TrashedItem {
+    Fractal(Fractal),
+    Knowledge(Knowledge),
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashEntry {
+    pub item: TrashedItem,
+    pub deleted_at: DateTime<Utc>,
+    pub deleted_by: Option<String>,
+}
+
+/// How many items [`purge_trash`] removed for good.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeReport {
+    pub fractals: usize,
+    pub knowledge: usize,
+}
+
+/// Lists every deleted fractal and knowledge entry, most recently deleted first.
+pub fn get_trash(conn: &Connection) -> Result<Vec<TrashEntry>, DataError> {
+    let mut entries = Vec::new();
+
+    let query = "
+        MATCH (f:Fractal)
+        WHERE f.deletedAt IS NOT NULL
+        RETURN f.id, f.name, f.createdAt, f.updatedAt, f.deletedAt, f.deletedBy
+    ";
+    for row in conn.query(query)? {
+        entries.push(row_to_trash_entry(
+            &row,
+            TrashedItem::Fractal(row_to_fractal(&row)?),
+        )?);
+    }
+
+    let query = "
+        MATCH (k:Knowledge)
+        WHERE k.deletedAt IS NOT NULL
+        RETURN k.id, k.content, k.createdAt, k.updatedAt, k.deletedAt, k.deletedBy
+    ";
+    for row in conn.query(query)? {
+        entries.push(row_to_trash_entry(
+            &row,
+            TrashedItem::Knowledge(row_to_knowledge(&row)?),
+        )?);
+    }
+
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+    Ok(entries)
+}
+
+fn row_to_trash_entry(row: &[Value], item: TrashedItem) -> Result<TrashEntry, DataError> {
+    let deleted_by = match &row[5] {
+        Value::Null(_) => None,
+        value => Some(extract_string(value, "deletedBy")?),
+    };
+
+    Ok(TrashEntry {
+        item,
+        deleted_at: extract_datetime(&row[4], "deletedAt")?,
+        deleted_by,
+    })
+}
+
+/// Takes a deleted fractal out of the trash together with everything deleted along with it,
+/// i.e. the descendants of a cascade and the knowledge they owned. Their edges were kept, so
+/// the restored fractals reappear where they were, and the links a reparenting delete added
+/// for their children are removed again.
+pub fn restore_fractal(conn: &Connection, id: &Uuid) -> Result<Fractal, DataError> {
+    with_transaction(conn, |conn| {
+        let deletion_id = get_deletion_id(conn, "Fractal", id)?
+            .ok_or_else(|| DataError::FractalNotFound(id.to_string()))?;
+
+        restore_deletion(conn, &deletion_id)?;
+        get_fractal_by_id(conn, id)
+    })
+}
+
+/// Takes a deleted knowledge entry out of the trash. Knowledge deleted with its fractal comes
+/// back by restoring the fractal, so this fails with [`DataError::FractalNotFound`] while the
+/// owning fractal is still in the trash.
+pub fn restore_knowledge(conn: &Connection, id: &Uuid) -> Result<Knowledge, DataError> {
+    with_transaction(conn, |conn| {
+        get_deletion_id(conn, "Knowledge", id)?
+            .ok_or_else(|| DataError::KnowledgeNotFound(id.to_string()))?;
+
+        let query = "
+            MATCH (f:Fractal)-[:HAS_KNOWLEDGE]->(k:Knowledge {id: $id})
+            RETURN f.id, f.deletedAt IS NOT NULL
+        ";
+        let params = vec![("id", Value::UUID(*id))];
+        let mut stmt = conn.prepare(query)?;
+        let result = conn.execute(&mut stmt, params)?;
+        for row in result {
+            if let Value::Bool(true) = row[1] {
+                let owner_id = extract_uuid(&row[0], "id")?;
+                return Err(DataError::FractalNotFound(owner_id.to_string()));
+            }
+        }
+
+        clear_deletion(
+            conn,
+            "Knowledge",
+            "n.id = $id",
+            vec![("id", Value::UUID(*id))],
+        )?;
+        get_knowledge_by_id(conn, id)
+    })
+}
+
+/// Removes for good everything that was moved to the trash before `older_than`. The server
+/// only calls this at startup with the configured retention period.
+///
+/// Links that only apply in the context of a purged fractal go with it, as do the context tags
+/// it left on knowledge, so no `context_id` is left pointing at a fractal that no longer exists.
+pub fn purge_trash(conn: &Connection, older_than: DateTime<Utc>) -> Result<PurgeReport, DataError> {
+    with_transaction(conn, |conn| {
+        let cutoff = OffsetDateTime::from(SystemTime::from(older_than));
+
+        keep_purged_relinks(conn, cutoff)?;
+        remove_purged_contexts(conn, cutoff)?;
+
+        Ok(PurgeReport {
+            knowledge: purge_table(conn, "Knowledge", cutoff)?,
+            fractals: purge_table(conn, "Fractal", cutoff)?,
+        })
+    })
+}
+
+fn purge_table(conn: &Connection, table: &str, cutoff: OffsetDateTime) -> Result<usize, DataError> {
+    let query = format!(
+        "
+        MATCH (n:{})
+        WHERE n.deletedAt IS NOT NULL AND n.deletedAt < $cutoff
+        DETACH DELETE n
+        RETURN count(n)
+        ",
+        table
+    );
+    let params = vec![("cutoff", Value::Timestamp(cutoff))];
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    match result.into_iter().next().as_deref() {
+        Some([Value::Int64(count)]) => Ok(*count as usize),
+        row => Err(DataError::InvalidData(format!(
+            "Expected purged count, found {:?}",
+            row
+        ))),
+    }
+}
+
+/// Links added by reparenting deletes that can no longer be restored become ordinary links.
+fn keep_purged_relinks(conn: &Connection, cutoff: OffsetDateTime) -> Result<(), DataError> {
+    let query = "
+        MATCH (f:Fractal)
+        WHERE f.deletedAt IS NOT NULL AND f.deletedAt < $cutoff
+        WITH DISTINCT f.deletionId AS deletion_id
+        MATCH (:Fractal)-[r:HAS_CHILD]->(:Fractal)
+        WHERE r.deletionId = deletion_id
+        SET r.deletionId = NULL
+    ";
+    let params = vec![("cutoff", Value::Timestamp(cutoff))];
+    let mut stmt = conn.prepare(query)?;
+    conn.execute(&mut stmt, params)?;
+    Ok(())
+}
+
+fn remove_purged_contexts(conn: &Connection, cutoff: OffsetDateTime) -> Result<(), DataError> {
+    let params = vec![("cutoff", Value::Timestamp(cutoff))];
+    for query in [
+        "
+        MATCH (c:Fractal)
+        WHERE c.deletedAt IS NOT NULL AND c.deletedAt < $cutoff
+        MATCH (:Fractal)-[r:HAS_CHILD]->(:Fractal)
+        WHERE r.context_id = c.id
+        DELETE r
+        ",
+        "
+        MATCH (:Knowledge)-[r:IN_CONTEXT]->(c:Fractal)
+        WHERE c.deletedAt IS NOT NULL AND c.deletedAt < $cutoff
+        DELETE r
+        ",
+    ] {
+        let mut stmt = conn.prepare(query)?;
+        conn.execute(&mut stmt, params.clone())?;
+    }
+    Ok(())
+}
+
+/// Returns the deletion the node belongs to, or `None` when it is not in the trash.
+fn get_deletion_id(conn: &Connection, table: &str, id: &Uuid) -> Result<Option<Uuid>, DataError> {
+    let query = format!(
+        "
+        MATCH (n:{} {{id: $id}})
+        WHERE n.deletedAt IS NOT NULL
+        RETURN n.deletionId
+        ",
+        table
+    );
+    let params = vec![("id", Value::UUID(*id))];
+    let mut stmt = conn.prepare(&query)?;
+    let result = conn.execute(&mut stmt, params)?;
+
+    result
+        .into_iter()
+        .next()
+        .map(|row| extract_uuid(&row[0], "deletionId"))
+        .transpose()
+}
+
+fn restore_deletion(conn: &Connection, deletion_id: &Uuid) -> Result<(), DataError> {
+    let query = "
+        MATCH (:Fractal)-[r:HAS_CHILD]->(:Fractal)
+        WHERE r.deletionId = $deletion_id
+        DELETE r
+    ";
+    let params = vec![("deletion_id", Value::UUID(*deletion_id))];
+    let mut stmt = conn.prepare(query)?;
+    conn.execute(&mut stmt, params)?;
+
+    for table in ["Fractal", "Knowledge"] {
+        clear_deletion(
+            conn,
+            table,
+            "n.deletionId = $deletion_id",
+            vec![("deletion_id", Value::UUID(*deletion_id))],
+        )?;
+    }
+    Ok(())
+}
+
+fn clear_deletion(
+    conn: &Connection,
+    table: &str,
+    condition: &str,
+    params: Vec<(&str, Value)>,
+) -> Result<(), DataError> {
+    let query = format!(
+        "
+        MATCH (n:{})
+        WHERE {} AND n.deletedAt IS NOT NULL
+        SET n.deletedAt = NULL, n.deletedBy = NULL, n.deletionId = NULL
+        ",
+        table, condition
+    );
+    let mut stmt = conn.prepare(&query)?;
+    conn.execute(&mut stmt, params)?;
+    Ok(())
+}
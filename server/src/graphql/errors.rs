@@ -1,12 +1,13 @@
 // fractal-project/server/src/graphql/errors.rs
 
+use crate::data::DataError;
 use async_graphql::ErrorExtensions;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum GraphQLError {
     #[error("Database error: {0}")]
-    DatabaseError(#[from] crate::data::DataError),
+    DatabaseError(DataError),
 
     #[error("Not found: {0}")]
     NotFound(String),
@@ -14,6 +15,18 @@ pub enum GraphQLError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Ambiguous name: {0}")]
+    AmbiguousName(String),
+
+    #[error("Invalid relation: {0}")]
+    SelfRelation(String),
+
+    #[error("Invalid relation: {0}")]
+    RelationCycle(String),
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
@@ -21,6 +34,37 @@ pub enum GraphQLError {
     InternalServerError,
 }
 
+/// Sorts data layer errors into the error a client can act on, anything unexpected stays a
+/// `DATABASE_ERROR`.
+impl From<DataError> for GraphQLError {
+    fn from(e: DataError) -> Self {
+        match e {
+            DataError::FractalNotFound(id) => {
+                GraphQLError::NotFound(format!("Fractal '{}' not found", id))
+            }
+            DataError::KnowledgeNotFound(id) => {
+                GraphQLError::NotFound(format!("Knowledge '{}' not found", id))
+            }
+            DataError::FractalsNotFound(_)
+            | DataError::RelationNotFound { .. }
+            | DataError::PathSegmentNotFound { .. } => GraphQLError::NotFound(e.to_string()),
+            DataError::FractalAlreadyExists(name) => {
+                GraphQLError::InvalidInput(format!("Fractal '{}' already exists", name))
+            }
+            DataError::StaleUpdate { .. } | DataError::FractalHasChildren { .. } => {
+                GraphQLError::Conflict(e.to_string())
+            }
+            DataError::RootFractalProtected | DataError::InvalidAlias(_) => {
+                GraphQLError::InvalidInput(e.to_string())
+            }
+            DataError::AmbiguousName { .. } => GraphQLError::AmbiguousName(e.to_string()),
+            DataError::SelfRelation(_) => GraphQLError::SelfRelation(e.to_string()),
+            DataError::RelationCycle { .. } => GraphQLError::RelationCycle(e.to_string()),
+            _ => GraphQLError::DatabaseError(e),
+        }
+    }
+}
+
 impl ErrorExtensions for GraphQLError {
     fn extend(&self) -> async_graphql::Error {
         async_graphql::Error::new(format!("{}", self)).extend_with(|_, e| match self {
@@ -33,6 +77,18 @@ impl ErrorExtensions for GraphQLError {
             GraphQLError::InvalidInput(_) => {
                 e.set("code", "INVALID_INPUT");
             }
+            GraphQLError::Conflict(_) => {
+                e.set("code", "CONFLICT");
+            }
+            GraphQLError::AmbiguousName(_) => {
+                e.set("code", "AMBIGUOUS_NAME");
+            }
+            GraphQLError::SelfRelation(_) => {
+                e.set("code", "SELF_RELATION");
+            }
+            GraphQLError::RelationCycle(_) => {
+                e.set("code", "RELATION_CYCLE");
+            }
             GraphQLError::Unauthorized(_) => {
                 e.set("code", "UNAUTHORIZED");
             }
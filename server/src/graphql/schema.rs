@@ -2,12 +2,15 @@ use super::errors::GraphQLError;
 use std::sync::Arc;
 
 use crate::data::{self, Fractal};
+use async_graphql::connection::{self, Connection, CursorType, Edge};
 use async_graphql::{
-    Context, EmptySubscription, InputObject, MergedObject, Object, Result, Schema,
+    Context, EmptySubscription, Enum, InputObject, Interface, MergedObject, Object, Result,
+    ResultExt, Schema,
 };
 use chrono::{DateTime, Utc};
 use kuzu::Database;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Default)]
@@ -16,8 +19,63 @@ pub struct FractalMutations;
 #[derive(InputObject)]
 struct CreateFractalInput {
     name: String,
-    parent_id: Uuid,
+    /// Parent to create the fractal under, once per entry of `contextIds` or without a context
+    /// when `contextIds` is empty.
+    parent_id: Option<Uuid>,
+    #[graphql(default)]
     context_ids: Vec<Uuid>,
+    /// Further parents, each with its own optional context.
+    #[graphql(default)]
+    parents: Vec<ParentLinkInput>,
+    /// Fractals the new fractal has as context, see `Fractal.contexts`.
+    #[graphql(default)]
+    contexts: Vec<Uuid>,
+}
+
+#[derive(InputObject)]
+struct ParentLinkInput {
+    parent_id: Uuid,
+    context_id: Option<Uuid>,
+}
+
+/// Gives the copy of the fractal `id` the name `to`.
+#[derive(InputObject)]
+struct RenameInput {
+    id: Uuid,
+    to: String,
+}
+
+impl CreateFractalInput {
+    fn parent_links(&self) -> Vec<data::ParentLink> {
+        let mut links = Vec::new();
+        if let Some(parent_id) = self.parent_id {
+            if self.context_ids.is_empty() {
+                links.push(data::ParentLink {
+                    parent_id,
+                    context_id: None,
+                });
+            }
+            for context_id in &self.context_ids {
+                links.push(data::ParentLink {
+                    parent_id,
+                    context_id: Some(*context_id),
+                });
+            }
+        }
+        links.extend(self.parents.iter().map(|p| data::ParentLink {
+            parent_id: p.parent_id,
+            context_id: p.context_id,
+        }));
+        links
+    }
+}
+
+#[derive(InputObject)]
+struct UpdateFractalInput {
+    id: Uuid,
+    name: Option<String>,
+    /// When set, the update is rejected unless the fractal's `updatedAt` still has this value.
+    expected_updated_at: Option<DateTime<Utc>>,
 }
 
 #[derive(InputObject)]
@@ -27,6 +85,64 @@ struct AddKnowledgeInput {
     context: Vec<Uuid>,
 }
 
+/// What happens to the children of a deleted fractal.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Default)]
+enum DeleteMode {
+    /// Refuse to delete a fractal that still has children.
+    #[default]
+    Restrict,
+    /// Link the children to every parent of the deleted fractal.
+    Reparent,
+    /// Also delete the descendants that have no parent outside of the deleted subtree.
+    Cascade,
+}
+
+impl From<DeleteMode> for data::DeleteMode {
+    fn from(mode: DeleteMode) -> Self {
+        match mode {
+            DeleteMode::Restrict => data::DeleteMode::Restrict,
+            DeleteMode::Reparent => data::DeleteMode::Reparent,
+            DeleteMode::Cascade => data::DeleteMode::Cascade,
+        }
+    }
+}
+
+/// Kinds of edges followed by `subgraph` and `paths`.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+// Named after the edge tables, e.g. `HAS_CHILD`
+#[allow(clippy::enum_variant_names)]
+enum RelationType {
+    HasChild,
+    HasContext,
+    HasKnowledge,
+}
+
+impl From<RelationType> for data::RelationType {
+    fn from(relation_type: RelationType) -> Self {
+        match relation_type {
+            RelationType::HasChild => data::RelationType::HasChild,
+            RelationType::HasContext => data::RelationType::HasContext,
+            RelationType::HasKnowledge => data::RelationType::HasKnowledge,
+        }
+    }
+}
+
+impl From<data::RelationType> for RelationType {
+    fn from(relation_type: data::RelationType) -> Self {
+        match relation_type {
+            data::RelationType::HasChild => RelationType::HasChild,
+            data::RelationType::HasContext => RelationType::HasContext,
+            data::RelationType::HasKnowledge => RelationType::HasKnowledge,
+        }
+    }
+}
+
+#[derive(InputObject)]
+struct UpdateKnowledgeInput {
+    id: Uuid,
+    content: String,
+}
+
 #[Object]
 impl FractalMutations {
     async fn create_fractal(
@@ -35,35 +151,105 @@ impl FractalMutations {
         input: CreateFractalInput,
     ) -> Result<FractalGraphQL> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
-        let context_id = input.context_ids.first().cloned();
+        if input.parent_id.is_none() && !input.context_ids.is_empty() {
+            return Err(GraphQLError::InvalidInput(
+                "'contextIds' requires 'parentId'".to_string(),
+            ))
+            .extend();
+        }
 
-        let fractal = data::create_fractal(
-            &conn,
-            &input.name,
-            Some(&input.parent_id),
-            context_id.as_ref(),
-        )
-        .map_err(|e| match e {
-            data::DataError::FractalAlreadyExists(_) => {
-                GraphQLError::InvalidInput(format!("Fractal '{}' already exists", input.name))
-            }
-            _ => GraphQLError::from(e),
-        })?;
+        let parents = input.parent_links();
+        if parents.is_empty() {
+            return Err(GraphQLError::InvalidInput(
+                "Specify 'parentId' or at least one of 'parents'".to_string(),
+            ))
+            .extend();
+        }
+
+        let fractal =
+            data::create_fractal_with_parents(&conn, &input.name, &parents, &input.contexts)
+                .map_err(GraphQLError::from)
+                .extend()?;
 
         Ok(FractalGraphQL::from(fractal))
     }
 
-    async fn delete_fractal(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
+    async fn update_fractal(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateFractalInput,
+    ) -> Result<FractalGraphQL> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let update = data::FractalUpdate { name: input.name };
 
-        data::delete_fractal(&conn, &id)
+        let fractal = data::update_fractal(&conn, &input.id, &update, input.expected_updated_at)
             .map_err(GraphQLError::from)
-            .map_err(Into::into)
+            .extend()?;
+
+        Ok(FractalGraphQL::from(fractal))
     }
 
+    /// Lets the fractal also be looked up as `alias`. Fails when a fractal next to it is already
+    /// called `alias` or has it as an alias.
+    async fn add_alias(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        alias: String,
+    ) -> Result<FractalGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let fractal = data::add_alias(&conn, &id, &alias)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(FractalGraphQL::from(fractal))
+    }
+
+    async fn remove_alias(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        alias: String,
+    ) -> Result<FractalGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let fractal = data::remove_alias(&conn, &id, &alias)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(FractalGraphQL::from(fractal))
+    }
+
+    /// Moves a fractal and its knowledge to the trash. `mode` decides what happens to its
+    /// children, with `dryRun` nothing is deleted and the result lists what would be.
+    /// `deletedBy` is shown in the trash.
+    async fn delete_fractal(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        #[graphql(default)] mode: DeleteMode,
+        #[graphql(default)] dry_run: bool,
+        deleted_by: Option<String>,
+    ) -> Result<DeletionReportGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let report = data::delete_fractal(&conn, &id, mode.into(), dry_run, deleted_by.as_deref())
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(DeletionReportGraphQL::from_report(report, dry_run))
+    }
+
+    /// Links `childId` under `parentId` in `contextId`. Returns whether a new link was created,
+    /// `false` when the same link already existed.
     async fn add_relation(
         &self,
         ctx: &Context<'_>,
@@ -72,12 +258,136 @@ impl FractalMutations {
         context_id: Option<Uuid>,
     ) -> Result<bool> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
         data::add_has_child_edge(&conn, &parent_id, &child_id, context_id.as_ref())
-            .map_err(GraphQLError::from)?;
+            .map_err(GraphQLError::from)
+            .extend()
+    }
+
+    /// Removes the link from `parentId` to `childId` in `contextId`, or the link without a
+    /// context when `contextId` is omitted. Returns whether a link was removed.
+    async fn remove_relation(
+        &self,
+        ctx: &Context<'_>,
+        parent_id: Uuid,
+        child_id: Uuid,
+        context_id: Option<Uuid>,
+    ) -> Result<bool> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        data::remove_has_child_edge(&conn, &parent_id, &child_id, context_id.as_ref())
+            .map_err(GraphQLError::from)
+            .extend()
+    }
+
+    /// Moves the link from `parentId` to `childId` in `contextId` to `newContextId`. Omitted
+    /// contexts stand for the link without a context. Returns whether a link was updated.
+    async fn update_relation_context(
+        &self,
+        ctx: &Context<'_>,
+        parent_id: Uuid,
+        child_id: Uuid,
+        context_id: Option<Uuid>,
+        new_context_id: Option<Uuid>,
+    ) -> Result<bool> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
-        Ok(true)
+        data::update_has_child_edge_context(
+            &conn,
+            &parent_id,
+            &child_id,
+            context_id.as_ref(),
+            new_context_id.as_ref(),
+        )
+        .map_err(GraphQLError::from)
+        .extend()
+    }
+
+    /// Moves `id` from under `fromParentId` to under `toParentId`. `contextId` picks the link to
+    /// move, it keeps its context.
+    async fn move_fractal(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        from_parent_id: Uuid,
+        to_parent_id: Uuid,
+        context_id: Option<Uuid>,
+    ) -> Result<FractalGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let fractal = data::move_fractal(
+            &conn,
+            &id,
+            &from_parent_id,
+            &to_parent_id,
+            context_id.as_ref(),
+        )
+        .map_err(GraphQLError::from)
+        .extend()?;
+
+        Ok(FractalGraphQL::from(fractal))
+    }
+
+    /// Copies `id` with all of its descendants and their knowledge under `toParentId` in
+    /// `contextId`. Copies are named after `renames`, or get `suffix` appended to their name.
+    /// Returns the copies, the copy of `id` first.
+    async fn copy_subtree(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        to_parent_id: Uuid,
+        context_id: Option<Uuid>,
+        #[graphql(default)] renames: Vec<RenameInput>,
+        #[graphql(default_with = "data::CopyNaming::default().suffix")] suffix: String,
+    ) -> Result<Vec<FractalGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let naming = data::CopyNaming {
+            renames: renames.into_iter().map(|r| (r.id, r.to)).collect(),
+            suffix,
+        };
+
+        let copies = data::copy_subtree(&conn, &id, &to_parent_id, context_id.as_ref(), &naming)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(copies.into_iter().map(FractalGraphQL::from).collect())
+    }
+
+    /// Gives `fractalId` the context `contextId`. Returns whether it was added, `false` when the
+    /// fractal already had that context.
+    async fn add_context(
+        &self,
+        ctx: &Context<'_>,
+        fractal_id: Uuid,
+        context_id: Uuid,
+    ) -> Result<bool> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        data::add_has_context_edge(&conn, &fractal_id, &context_id)
+            .map_err(GraphQLError::from)
+            .extend()
+    }
+
+    /// Removes the context `contextId` from `fractalId`. Returns whether the fractal had it.
+    async fn remove_context(
+        &self,
+        ctx: &Context<'_>,
+        fractal_id: Uuid,
+        context_id: Uuid,
+    ) -> Result<bool> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        data::remove_has_context_edge(&conn, &fractal_id, &context_id)
+            .map_err(GraphQLError::from)
+            .extend()
     }
 
     async fn add_knowledge(
@@ -86,13 +396,110 @@ impl FractalMutations {
         input: AddKnowledgeInput,
     ) -> Result<KnowledgeGraphQL> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
         let knowledge =
             data::add_knowledge(&conn, &input.fractal_id, &input.content, &input.context)
-                .map_err(GraphQLError::from)?;
+                .map_err(GraphQLError::from)
+                .extend()?;
+
+        Ok(KnowledgeGraphQL::from(knowledge))
+    }
+
+    async fn update_knowledge(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateKnowledgeInput,
+    ) -> Result<KnowledgeGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let knowledge = data::update_knowledge(&conn, &input.id, &input.content)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(KnowledgeGraphQL::from(knowledge))
+    }
+
+    /// Replaces the contexts of a knowledge entry.
+    async fn set_knowledge_contexts(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        context_ids: Vec<Uuid>,
+    ) -> Result<KnowledgeGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let knowledge = data::set_knowledge_contexts(&conn, &id, &context_ids)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(KnowledgeGraphQL::from(knowledge))
+    }
+
+    /// Moves a knowledge entry to another fractal.
+    async fn move_knowledge(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        fractal_id: Uuid,
+    ) -> Result<KnowledgeGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let knowledge = data::move_knowledge(&conn, &id, &fractal_id)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(KnowledgeGraphQL::from(knowledge))
+    }
+
+    /// Moves a knowledge entry to the trash. `deletedBy` is shown in the trash.
+    async fn delete_knowledge(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        deleted_by: Option<String>,
+    ) -> Result<bool> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        match data::delete_knowledge(&conn, &id, deleted_by.as_deref())
+            .map_err(GraphQLError::from)
+            .extend()?
+        {
+            true => Ok(true),
+            false => Err(GraphQLError::NotFound(format!(
+                "Knowledge '{}' not found",
+                id
+            )))
+            .extend(),
+        }
+    }
+
+    /// Takes a fractal out of the trash together with everything that was deleted with it.
+    async fn restore_fractal(&self, ctx: &Context<'_>, id: Uuid) -> Result<FractalGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let fractal = data::restore_fractal(&conn, &id)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(FractalGraphQL::from(fractal))
+    }
+
+    /// Takes a knowledge entry out of the trash. Fails while its fractal is still in the trash.
+    async fn restore_knowledge(&self, ctx: &Context<'_>, id: Uuid) -> Result<KnowledgeGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
-        Ok(KnowledgeGraphQL::from_knowledge(knowledge)?)
+        let knowledge = data::restore_knowledge(&conn, &id)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(KnowledgeGraphQL::from(knowledge))
     }
 }
 
@@ -104,49 +511,280 @@ pub struct FractalQueries;
 
 #[Object]
 impl FractalQueries {
-    async fn fractal(&self, ctx: &Context<'_>, name: Option<String>) -> Result<FractalGraphQL> {
+    /// Looks a fractal up by `id` or by `name`, defaulting to Root when neither is given. Names
+    /// are only unique per parent and context, so a `name` shared by several fractals fails with
+    /// `AMBIGUOUS_NAME`; `fractalsByName` and `fractalByPath` tell them apart.
+    async fn fractal(
+        &self,
+        ctx: &Context<'_>,
+        name: Option<String>,
+        id: Option<Uuid>,
+    ) -> Result<FractalGraphQL> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
-        let name = name.unwrap_or("Root".to_string());
-        let fractal = data::get_fractal_by_name(&conn, &name).map_err(|e| match e {
-            data::DataError::FractalNotFound(_) => {
-                GraphQLError::NotFound(format!("Fractal '{}' not found", name))
+        let fractal = match (id, &name) {
+            (Some(_), Some(_)) => {
+                return Err(GraphQLError::InvalidInput(
+                    "Specify either 'id' or 'name', not both".to_string(),
+                ))
+                .extend()
             }
-            _ => GraphQLError::from(e),
-        })?;
-
-        Ok(FractalGraphQL {
-            id: fractal.id,
-            name: fractal.name,
-            created_at: fractal.created_at,
-            updated_at: fractal.updated_at,
-            parents: vec![],
-            children: vec![],
-            contexts: vec![],
-        })
+            (Some(id), None) => data::get_fractal_by_id(&conn, &id),
+            (None, Some(name)) => data::get_fractal_by_name(&conn, name),
+            (None, None) => data::get_root_fractal(&conn),
+        }
+        .map_err(GraphQLError::from)
+        .extend()?;
+
+        let mut result = FractalGraphQL::from(fractal);
+        // Found by name but called differently, so one of its aliases matched
+        result.matched_alias = name.filter(|name| *name != result.name);
+        Ok(result)
+    }
+
+    /// Every fractal called `name` or having it as an alias, with the paths leading to it from
+    /// Root, since names are only unique among the children a parent has in one context.
+    async fn fractals_by_name(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> Result<Vec<NameCandidateGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let candidates = data::find_fractals_by_name(&conn, &name).map_err(GraphQLError::from)?;
+
+        Ok(candidates
+            .into_iter()
+            .map(NameCandidateGraphQL::from)
+            .collect())
+    }
+
+    /// Looks a fractal up by the names on the way down from Root, e.g. `Programming/Rust/String`.
+    /// Links in a context are only followed when that context is part of the path.
+    async fn fractal_by_path(&self, ctx: &Context<'_>, path: String) -> Result<FractalGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let resolved = data::resolve_path(&conn, &path)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        let fractal = resolved
+            .fractals
+            .into_iter()
+            .last()
+            .ok_or(GraphQLError::InternalServerError)?;
+        Ok(FractalGraphQL::from(fractal))
+    }
+
+    /// Looks several fractals up by id. Ids that do not exist resolve to `null`, positions match
+    /// the requested `ids`.
+    async fn fractals(
+        &self,
+        ctx: &Context<'_>,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<Option<FractalGraphQL>>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let found: HashMap<Uuid, Fractal> = data::get_fractals_by_ids(&conn, &ids)
+            .map_err(GraphQLError::from)?
+            .into_iter()
+            .map(|f| (f.id, f))
+            .collect();
+
+        Ok(ids
+            .iter()
+            .map(|id| found.get(id).cloned().map(FractalGraphQL::from))
+            .collect())
+    }
+
+    /// Fetches any object by its globally unique id.
+    async fn node(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<Node>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        match data::get_fractal_by_id(&conn, &id) {
+            Ok(fractal) => return Ok(Some(Node::Fractal(FractalGraphQL::from(fractal)))),
+            Err(data::DataError::FractalNotFound(_)) => {}
+            Err(e) => return Err(GraphQLError::from(e).into()),
+        }
+
+        match data::get_knowledge_by_id(&conn, &id) {
+            Ok(knowledge) => Ok(Some(Node::Knowledge(KnowledgeGraphQL::from(knowledge)))),
+            Err(data::DataError::KnowledgeNotFound(_)) => Ok(None),
+            Err(e) => Err(GraphQLError::from(e).into()),
+        }
     }
 
+    /// All knowledge of the named fractal that is tagged with every one of `context`, oldest
+    /// first.
     async fn knowledge(
         &self,
         ctx: &Context<'_>,
         fractal_name: String,
         context: Vec<Uuid>,
-    ) -> Result<KnowledgeGraphQL> {
+    ) -> Result<Vec<KnowledgeGraphQL>> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
         let knowledge = data::get_fractal_knowledge_with_context(&conn, &fractal_name, &context)
-            .map_err(GraphQLError::from)?;
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(knowledge.into_iter().map(KnowledgeGraphQL::from).collect())
+    }
+
+    /// Knowledge of the named fractal ranked by how well it matches `context`, best match
+    /// first. Knowledge tagged with an ancestor of a requested context counts partially.
+    async fn best_knowledge(
+        &self,
+        ctx: &Context<'_>,
+        fractal_name: String,
+        context: Vec<Uuid>,
+        first: Option<i32>,
+    ) -> Result<Vec<RankedKnowledgeGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+        let limit = first_to_limit(first)?;
+
+        let ranked = data::rank_fractal_knowledge_by_name(&conn, &fractal_name, &context, limit)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(ranked
+            .into_iter()
+            .map(RankedKnowledgeGraphQL::from)
+            .collect())
+    }
 
-        if knowledge.is_empty() {
-            return Err(GraphQLError::NotFound("Knowledge not found".to_string()).into());
+    /// Everything reachable from `rootId` in at most `depth` steps as flat, deduplicated lists
+    /// of nodes and edges. Only edges of `relationTypes` are followed, all of them when omitted,
+    /// and only `HAS_CHILD` edges in exactly `contextId` when it is given. At most `maxNodes`
+    /// nodes are returned, nearest first, and `truncated` tells whether any were left out.
+    async fn subgraph(
+        &self,
+        ctx: &Context<'_>,
+        root_id: Uuid,
+        depth: i32,
+        relation_types: Option<Vec<RelationType>>,
+        context_id: Option<Uuid>,
+        max_nodes: Option<i32>,
+    ) -> Result<SubgraphGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+        let depth = depth_to_limit(depth, "depth")?;
+        let max_nodes = match max_nodes {
+            None => data::MAX_SUBGRAPH_NODES,
+            Some(max_nodes) => depth_to_limit(max_nodes, "maxNodes")?,
+        };
+        if !(1..=data::MAX_SUBGRAPH_NODES).contains(&max_nodes) {
+            return Err(GraphQLError::InvalidInput(format!(
+                "'maxNodes' must be between 1 and {}",
+                data::MAX_SUBGRAPH_NODES
+            )))
+            .extend();
         }
 
-        Ok(KnowledgeGraphQL {
-            id: knowledge[0].id,
-            content: knowledge[0].content.clone(),
+        let relation_types: Vec<data::RelationType> = match relation_types {
+            Some(types) => types.into_iter().map(Into::into).collect(),
+            None => data::RelationType::ALL.to_vec(),
+        };
+
+        let subgraph = data::get_subgraph(
+            &conn,
+            &root_id,
+            depth,
+            &relation_types,
+            context_id.as_ref(),
+            max_nodes,
+        )
+        .map_err(GraphQLError::from)
+        .extend()?;
+
+        Ok(SubgraphGraphQL::from(subgraph))
+    }
+
+    /// Up to `k` shortest ways to get from `fromId` to `toId`, following `HAS_CHILD` and
+    /// `HAS_CONTEXT` edges in either direction. `relationTypes` narrows that down, `HAS_KNOWLEDGE`
+    /// is not allowed.
+    async fn paths(
+        &self,
+        ctx: &Context<'_>,
+        from_id: Uuid,
+        to_id: Uuid,
+        max_length: Option<i32>,
+        relation_types: Option<Vec<RelationType>>,
+        #[graphql(default = 1)] k: i32,
+    ) -> Result<Vec<PathGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+        let max_length = match max_length {
+            None => data::MAX_PATH_LENGTH,
+            Some(length) => depth_to_limit(length, "maxLength")?,
+        };
+        let k = depth_to_limit(k, "k")?;
+
+        let relation_types: Vec<data::RelationType> = match relation_types {
+            Some(types) if types.contains(&RelationType::HasKnowledge) => {
+                return Err(GraphQLError::InvalidInput(
+                    "Paths cannot follow 'HAS_KNOWLEDGE' edges".to_string(),
+                ))
+                .extend()
+            }
+            Some(types) => types.into_iter().map(Into::into).collect(),
+            None => vec![data::RelationType::HasChild, data::RelationType::HasContext],
+        };
+
+        let paths = data::find_paths(&conn, &from_id, &to_id, max_length, &relation_types, k)
+            .map_err(GraphQLError::from)
+            .extend()?;
+
+        Ok(paths.into_iter().map(PathGraphQL::from).collect())
+    }
+
+    /// Deleted fractals and knowledge, most recently deleted first.
+    async fn trash(&self, ctx: &Context<'_>) -> Result<Vec<TrashEntryGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let trash = data::get_trash(&conn).map_err(GraphQLError::from)?;
+
+        Ok(trash.into_iter().map(TrashEntryGraphQL::from).collect())
+    }
+}
+
+fn first_to_limit(first: Option<i32>) -> Result<Option<usize>> {
+    first
+        .map(|first| {
+            usize::try_from(first)
+                .map_err(|_| GraphQLError::InvalidInput("'first' must not be negative".to_string()))
         })
+        .transpose()
+        .extend()
+}
+
+/// `maxDepth` defaults to the deepest hierarchy walked anywhere and must not go beyond it.
+fn max_depth_to_limit(max_depth: Option<i32>) -> Result<usize> {
+    let Some(depth) = max_depth else {
+        return Ok(data::MAX_HIERARCHY_DEPTH);
+    };
+    let depth = depth_to_limit(depth, "maxDepth")?;
+    if depth > data::MAX_HIERARCHY_DEPTH {
+        return Err(GraphQLError::InvalidInput(format!(
+            "'maxDepth' must be at most {}",
+            data::MAX_HIERARCHY_DEPTH
+        )))
+        .extend();
     }
+    Ok(depth)
+}
+
+fn depth_to_limit(depth: i32, argument: &str) -> Result<usize> {
+    usize::try_from(depth)
+        .map_err(|_| GraphQLError::InvalidInput(format!("'{}' must not be negative", argument)))
+        .extend()
 }
 
 #[derive(InputObject)]
@@ -163,6 +801,7 @@ pub struct FractalGraphQL {
     parents: Vec<FractalGraphQL>,
     children: Vec<FractalGraphQL>,
     contexts: Vec<FractalGraphQL>,
+    matched_alias: Option<String>,
 }
 
 #[Object]
@@ -174,13 +813,28 @@ impl FractalGraphQL {
     async fn name(&self) -> String {
         self.name.clone()
     }
+
+    /// Other names the fractal can be looked up by.
+    async fn aliases(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        Ok(data::get_aliases(&conn, &self.id).map_err(GraphQLError::from)?)
+    }
+
+    /// The alias the fractal was looked up by in `fractal(name:)`, `null` when it was its name or
+    /// it was not looked up by name.
+    async fn matched_alias(&self) -> Option<&str> {
+        self.matched_alias.as_deref()
+    }
+
     async fn children(
         &self,
         ctx: &Context<'_>,
         input: Option<GetFractalChildrenInput>,
     ) -> Result<Vec<FractalGraphQL>> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
         let context_id = input.and_then(|i| i.context_id);
 
@@ -201,7 +855,7 @@ impl FractalGraphQL {
 
     async fn parents(&self, ctx: &Context<'_>) -> Result<Vec<FractalGraphQL>> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
         let children =
             data::get_fractal_relations(&conn, &self.id, "parents").map_err(GraphQLError::from)?;
@@ -211,18 +865,187 @@ impl FractalGraphQL {
 
     async fn contexts(&self, ctx: &Context<'_>) -> Result<Vec<FractalGraphQL>> {
         let db = ctx.data::<Arc<Database>>()?;
-        let conn = data::create_connection(&db).map_err(GraphQLError::from)?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
 
         let children =
             data::get_fractal_relations(&conn, &self.id, "contexts").map_err(GraphQLError::from)?;
 
         Ok(children.into_iter().map(FractalGraphQL::from).collect())
     }
+
+    /// Everything below this fractal up to `maxDepth` levels in one request, following only
+    /// links in exactly `contextId` when given. A fractal with several parents is listed once
+    /// per link.
+    async fn descendants(
+        &self,
+        ctx: &Context<'_>,
+        max_depth: Option<i32>,
+        context_id: Option<Uuid>,
+    ) -> Result<Vec<HierarchyEntryGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+        let max_depth = max_depth_to_limit(max_depth)?;
+
+        let descendants = data::get_descendants(&conn, &self.id, max_depth, context_id.as_ref())
+            .map_err(GraphQLError::from)?;
+
+        Ok(descendants
+            .into_iter()
+            .map(HierarchyEntryGraphQL::from)
+            .collect())
+    }
+
+    /// Everything above this fractal up to `maxDepth` levels in one request.
+    async fn ancestors(
+        &self,
+        ctx: &Context<'_>,
+        max_depth: Option<i32>,
+    ) -> Result<Vec<HierarchyEntryGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+        let max_depth = max_depth_to_limit(max_depth)?;
+
+        let ancestors =
+            data::get_ancestors(&conn, &self.id, max_depth).map_err(GraphQLError::from)?;
+
+        Ok(ancestors
+            .into_iter()
+            .map(HierarchyEntryGraphQL::from)
+            .collect())
+    }
+
+    /// Every way down from Root to this fractal with the context of each link, e.g. for
+    /// breadcrumbs. Links are only followed in a context that is Root or comes earlier on the
+    /// path, like in `fractalByPath`. With `contextId` the paths that apply in that context are
+    /// listed, following links in exactly `contextId` as well. With `throughId` only the paths
+    /// through that fractal are listed.
+    async fn paths_from_root(
+        &self,
+        ctx: &Context<'_>,
+        context_id: Option<Uuid>,
+        through_id: Option<Uuid>,
+    ) -> Result<Vec<PathGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let paths =
+            data::get_paths_from_root(&conn, &self.id, context_id.as_ref(), through_id.as_ref())
+                .map_err(GraphQLError::from)?;
+
+        Ok(paths.into_iter().map(PathGraphQL::from).collect())
+    }
+
+    /// Fractals that have this fractal as one of their `contexts`.
+    async fn provides_context_for(&self, ctx: &Context<'_>) -> Result<Vec<FractalGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let dependents = data::get_fractal_relations(&conn, &self.id, "provides_context_for")
+            .map_err(GraphQLError::from)?;
+
+        Ok(dependents.into_iter().map(FractalGraphQL::from).collect())
+    }
+
+    /// Knowledge of this fractal ranked by how well it matches `context`, best match first.
+    async fn best_knowledge(
+        &self,
+        ctx: &Context<'_>,
+        context: Vec<Uuid>,
+        first: Option<i32>,
+    ) -> Result<Vec<RankedKnowledgeGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+        let limit = first_to_limit(first)?;
+
+        let ranked = data::rank_fractal_knowledge(&conn, &self.id, &context, limit)
+            .map_err(GraphQLError::from)?;
+
+        Ok(ranked
+            .into_iter()
+            .map(RankedKnowledgeGraphQL::from)
+            .collect())
+    }
+
+    /// Knowledge of this fractal, oldest first. When `context` is given only knowledge tagged
+    /// with every one of those contexts is returned.
+    async fn knowledges(
+        &self,
+        ctx: &Context<'_>,
+        context: Option<Vec<Uuid>>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<Connection<data::KnowledgeCursor, KnowledgeGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let context = context.unwrap_or_default();
+
+        connection::query(
+            after,
+            None,
+            first,
+            None,
+            |after: Option<data::KnowledgeCursor>,
+             _: Option<data::KnowledgeCursor>,
+             first: Option<usize>,
+             _: Option<usize>| async move {
+                let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+                // Fetch one extra entry to find out whether there is a next page
+                let mut knowledge = data::get_fractal_knowledge(
+                    &conn,
+                    &self.id,
+                    &context,
+                    after.as_ref(),
+                    first.map(|first| first + 1),
+                )
+                .map_err(GraphQLError::from)?;
+
+                let has_next_page = first.is_some_and(|first| knowledge.len() > first);
+                if let Some(first) = first {
+                    knowledge.truncate(first);
+                }
+
+                let mut connection = Connection::new(after.is_some(), has_next_page);
+                for k in knowledge {
+                    let cursor = data::KnowledgeCursor::from(&k);
+                    connection
+                        .edges
+                        .push(Edge::new(cursor, KnowledgeGraphQL::from(k)));
+                }
+
+                Ok::<_, async_graphql::Error>(connection)
+            },
+        )
+        .await
+    }
+}
+
+/// Encoded as `<createdAt in microseconds>:<id>`, clients should treat it as opaque.
+impl CursorType for data::KnowledgeCursor {
+    type Error = GraphQLError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let invalid = || GraphQLError::InvalidInput(format!("Invalid cursor '{}'", s));
+        let (micros, id) = s.split_once(':').ok_or_else(invalid)?;
+        let created_at = micros
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+
+        Ok(data::KnowledgeCursor { created_at, id })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}", self.created_at.timestamp_micros(), self.id)
+    }
 }
 
-struct KnowledgeGraphQL {
+pub struct KnowledgeGraphQL {
     id: Uuid,
     content: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
 }
 
 #[Object]
@@ -235,11 +1058,356 @@ impl KnowledgeGraphQL {
         self.content.clone()
     }
 
-    async fn fractal(&self, ctx: &Context<'_>) -> FractalGraphQL {
-        FractalQueries
-            .fractal(ctx, Some("Root".to_string()))
-            .await
-            .unwrap()
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// The fractal this knowledge belongs to.
+    async fn fractal(&self, ctx: &Context<'_>) -> Result<FractalGraphQL> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let fractal = data::get_knowledge_fractal(&conn, &self.id).map_err(GraphQLError::from)?;
+
+        Ok(FractalGraphQL::from(fractal))
+    }
+
+    /// The fractals this knowledge applies in.
+    async fn contexts(&self, ctx: &Context<'_>) -> Result<Vec<FractalGraphQL>> {
+        let db = ctx.data::<Arc<Database>>()?;
+        let conn = data::create_connection(db).map_err(GraphQLError::from)?;
+
+        let contexts = data::get_knowledge_contexts(&conn, &self.id).map_err(GraphQLError::from)?;
+
+        Ok(contexts.into_iter().map(FractalGraphQL::from).collect())
+    }
+}
+
+/// What `deleteFractal` removed, or would remove on a dry run.
+pub struct DeletionReportGraphQL {
+    dry_run: bool,
+    deleted_fractals: Vec<FractalGraphQL>,
+    deleted_knowledge: Vec<KnowledgeGraphQL>,
+    reparented_fractals: Vec<FractalGraphQL>,
+}
+
+#[Object]
+impl DeletionReportGraphQL {
+    async fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Deleted fractals, the requested one first.
+    async fn deleted_fractals(&self) -> &[FractalGraphQL] {
+        &self.deleted_fractals
+    }
+
+    async fn deleted_knowledge(&self) -> &[KnowledgeGraphQL] {
+        &self.deleted_knowledge
+    }
+
+    /// Children that were linked to the parents of the deleted fractal.
+    async fn reparented_fractals(&self) -> &[FractalGraphQL] {
+        &self.reparented_fractals
+    }
+}
+
+impl DeletionReportGraphQL {
+    fn from_report(report: data::DeletionReport, dry_run: bool) -> Self {
+        DeletionReportGraphQL {
+            dry_run,
+            deleted_fractals: report
+                .fractals
+                .into_iter()
+                .map(FractalGraphQL::from)
+                .collect(),
+            deleted_knowledge: report
+                .knowledge
+                .into_iter()
+                .map(KnowledgeGraphQL::from)
+                .collect(),
+            reparented_fractals: report
+                .reparented
+                .into_iter()
+                .map(FractalGraphQL::from)
+                .collect(),
+        }
+    }
+}
+
+/// A deleted fractal or knowledge entry.
+pub struct TrashEntryGraphQL {
+    node: Node,
+    deleted_at: DateTime<Utc>,
+    deleted_by: Option<String>,
+}
+
+#[Object]
+impl TrashEntryGraphQL {
+    async fn node(&self) -> &Node {
+        &self.node
+    }
+
+    async fn deleted_at(&self) -> DateTime<Utc> {
+        self.deleted_at
+    }
+
+    async fn deleted_by(&self) -> Option<&str> {
+        self.deleted_by.as_deref()
+    }
+}
+
+impl From<data::TrashEntry> for TrashEntryGraphQL {
+    fn from(entry: data::TrashEntry) -> Self {
+        let node = match entry.item {
+            data::TrashedItem::Fractal(f) => Node::Fractal(FractalGraphQL::from(f)),
+            data::TrashedItem::Knowledge(k) => Node::Knowledge(KnowledgeGraphQL::from(k)),
+        };
+
+        TrashEntryGraphQL {
+            node,
+            deleted_at: entry.deleted_at,
+            deleted_by: entry.deleted_by,
+        }
+    }
+}
+
+/// A `HAS_CHILD` link, valid only in `contextId` when set.
+pub struct RelationGraphQL {
+    parent_id: Uuid,
+    child_id: Uuid,
+    context_id: Option<Uuid>,
+}
+
+#[Object]
+impl RelationGraphQL {
+    async fn parent_id(&self) -> Uuid {
+        self.parent_id
+    }
+
+    async fn child_id(&self) -> Uuid {
+        self.child_id
+    }
+
+    async fn context_id(&self) -> Option<Uuid> {
+        self.context_id
+    }
+}
+
+impl From<data::HierarchyEdge> for RelationGraphQL {
+    fn from(edge: data::HierarchyEdge) -> Self {
+        RelationGraphQL {
+            parent_id: edge.parent_id,
+            child_id: edge.child_id,
+            context_id: edge.context_id,
+        }
+    }
+}
+
+/// An edge of a `subgraph`. Only `HAS_CHILD` edges can have a `contextId`.
+pub struct EdgeGraphQL(data::GraphEdge);
+
+#[Object]
+impl EdgeGraphQL {
+    async fn relation_type(&self) -> RelationType {
+        self.0.relation_type.into()
+    }
+
+    async fn source_id(&self) -> Uuid {
+        self.0.source_id
+    }
+
+    async fn target_id(&self) -> Uuid {
+        self.0.target_id
+    }
+
+    async fn context_id(&self) -> Option<Uuid> {
+        self.0.context_id
+    }
+}
+
+/// Nodes and edges around a root, each listed once.
+pub struct SubgraphGraphQL {
+    nodes: Vec<Node>,
+    edges: Vec<EdgeGraphQL>,
+    truncated: bool,
+}
+
+#[Object]
+impl SubgraphGraphQL {
+    /// The root first, then the other fractals, then knowledge.
+    async fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    async fn edges(&self) -> &[EdgeGraphQL] {
+        &self.edges
+    }
+
+    /// Whether nodes within reach were left out because of `maxNodes`.
+    async fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl From<data::Subgraph> for SubgraphGraphQL {
+    fn from(subgraph: data::Subgraph) -> Self {
+        let fractals = subgraph
+            .fractals
+            .into_iter()
+            .map(|f| Node::Fractal(FractalGraphQL::from(f)));
+        let knowledge = subgraph
+            .knowledge
+            .into_iter()
+            .map(|k| Node::Knowledge(KnowledgeGraphQL::from(k)));
+
+        SubgraphGraphQL {
+            nodes: fractals.chain(knowledge).collect(),
+            edges: subgraph.edges.into_iter().map(EdgeGraphQL).collect(),
+            truncated: subgraph.truncated,
+        }
+    }
+}
+
+/// A way from one fractal to another found by `paths` or `pathsFromRoot`.
+pub struct PathGraphQL {
+    fractals: Vec<FractalGraphQL>,
+    edges: Vec<EdgeGraphQL>,
+}
+
+#[Object]
+impl PathGraphQL {
+    /// Every fractal on the path, in order.
+    async fn fractals(&self) -> &[FractalGraphQL] {
+        &self.fractals
+    }
+
+    /// The edge between each fractal and the next, with its stored direction.
+    async fn edges(&self) -> &[EdgeGraphQL] {
+        &self.edges
+    }
+
+    /// Number of edges.
+    async fn length(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl From<data::FractalPath> for PathGraphQL {
+    fn from(path: data::FractalPath) -> Self {
+        PathGraphQL {
+            fractals: path
+                .fractals
+                .into_iter()
+                .map(FractalGraphQL::from)
+                .collect(),
+            edges: path.edges.into_iter().map(EdgeGraphQL).collect(),
+        }
+    }
+}
+
+/// A fractal found by `fractalsByName`, with the paths that tell it apart from others.
+pub struct NameCandidateGraphQL {
+    fractal: FractalGraphQL,
+    paths: Vec<PathGraphQL>,
+    matched_alias: Option<String>,
+}
+
+#[Object]
+impl NameCandidateGraphQL {
+    async fn fractal(&self) -> &FractalGraphQL {
+        &self.fractal
+    }
+
+    /// Every way down from Root to the fractal, see `Fractal.pathsFromRoot`.
+    async fn paths(&self) -> &[PathGraphQL] {
+        &self.paths
+    }
+
+    /// The alias that matched, `null` when the fractal is called by the name itself.
+    async fn matched_alias(&self) -> Option<&str> {
+        self.matched_alias.as_deref()
+    }
+}
+
+impl From<data::NameCandidate> for NameCandidateGraphQL {
+    fn from(candidate: data::NameCandidate) -> Self {
+        NameCandidateGraphQL {
+            fractal: FractalGraphQL::from(candidate.fractal),
+            paths: candidate.paths.into_iter().map(PathGraphQL::from).collect(),
+            matched_alias: candidate.matched_alias,
+        }
+    }
+}
+
+/// A fractal found by `descendants` or `ancestors`, with the link it was reached through.
+pub struct HierarchyEntryGraphQL {
+    fractal: FractalGraphQL,
+    depth: usize,
+    relation: RelationGraphQL,
+}
+
+#[Object]
+impl HierarchyEntryGraphQL {
+    async fn fractal(&self) -> &FractalGraphQL {
+        &self.fractal
+    }
+
+    /// Levels away from the fractal the walk started at, `1` for direct children or parents.
+    async fn depth(&self) -> usize {
+        self.depth
+    }
+
+    async fn relation(&self) -> &RelationGraphQL {
+        &self.relation
+    }
+}
+
+impl From<data::HierarchyEntry> for HierarchyEntryGraphQL {
+    fn from(entry: data::HierarchyEntry) -> Self {
+        HierarchyEntryGraphQL {
+            fractal: FractalGraphQL::from(entry.fractal),
+            depth: entry.depth,
+            relation: RelationGraphQL::from(entry.edge),
+        }
+    }
+}
+
+/// A knowledge entry together with how well it matches the requested contexts.
+pub struct RankedKnowledgeGraphQL {
+    knowledge: KnowledgeGraphQL,
+    score: f64,
+    matched_context_ids: Vec<Uuid>,
+}
+
+#[Object]
+impl RankedKnowledgeGraphQL {
+    async fn knowledge(&self) -> &KnowledgeGraphQL {
+        &self.knowledge
+    }
+
+    /// Share of the requested contexts matched, from 0 to 1.
+    async fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Requested contexts that were matched, exactly or through one of their ancestors.
+    async fn matched_context_ids(&self) -> Vec<Uuid> {
+        self.matched_context_ids.clone()
+    }
+}
+
+impl From<data::RankedKnowledge> for RankedKnowledgeGraphQL {
+    fn from(r: data::RankedKnowledge) -> Self {
+        RankedKnowledgeGraphQL {
+            knowledge: KnowledgeGraphQL::from(r.knowledge),
+            score: r.score,
+            matched_context_ids: r.matched_context_ids,
+        }
     }
 }
 
@@ -253,20 +1421,31 @@ impl From<Fractal> for FractalGraphQL {
             parents: vec![],
             children: vec![],
             contexts: vec![],
+            matched_alias: None,
         }
     }
 }
 
+/// An object with a globally unique id that can be fetched with `node(id:)`.
+#[derive(Interface)]
+#[graphql(field(name = "id", ty = "Uuid"))]
+pub enum Node {
+    Fractal(FractalGraphQL),
+    Knowledge(KnowledgeGraphQL),
+}
+
 #[derive(MergedObject, Default)]
 pub struct QueryRoot(FractalQueries);
 
 pub type FractalSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
 
-impl KnowledgeGraphQL {
-    fn from_knowledge(k: data::Knowledge) -> Result<Self, GraphQLError> {
-        Ok(KnowledgeGraphQL {
+impl From<data::Knowledge> for KnowledgeGraphQL {
+    fn from(k: data::Knowledge) -> Self {
+        KnowledgeGraphQL {
             id: k.id,
             content: k.content,
-        })
+            created_at: k.created_at,
+            updated_at: k.updated_at,
+        }
     }
 }
@@ -1,7 +1,8 @@
 use kuzu::Database;
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::CorsLayer;
 
+pub mod config;
 pub mod data;
 pub mod graphql;
 
@@ -11,16 +12,17 @@ use axum::{
     http::Method,
     http::StatusCode,
     response::{self, IntoResponse},
-    routing::get,
+    routing::{get, post_service},
     serve::Serve,
     Router,
 };
+use config::Config;
 use graphql::{MutationRoot, QueryRoot};
 use tokio::net::TcpListener;
 
 pub type Server = Serve<Router<()>, Router<()>>;
 
-pub fn run(listener: TcpListener, db: Database) -> Result<Server, std::io::Error> {
+pub fn run(listener: TcpListener, db: Database, config: &Config) -> Result<Server, std::io::Error> {
     let state = Arc::new(db);
 
     let schema = Schema::build(
@@ -31,23 +33,35 @@ pub fn run(listener: TcpListener, db: Database) -> Result<Server, std::io::Error
     .data(state.clone())
     .finish();
 
+    let allow_origin = config
+        .allow_origin()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
     let cors = CorsLayer::new()
         // allow `GET` and `POST` when accessing the resource
         .allow_methods([Method::GET, Method::POST])
-        // allow requests from any origin
-        .allow_origin(Any);
+        // allow requests from the configured origins
+        .allow_origin(allow_origin);
+
+    let graphql_route = if config.graphiql {
+        get(graphiql).post_service(GraphQL::new(schema))
+    } else {
+        post_service(GraphQL::new(schema))
+    };
 
     let app = Router::new()
-        .route("/", get(graphiql).post_service(GraphQL::new(schema)))
+        .route("/", graphql_route)
         .route("/health_check", get(health_check))
         .layer(cors)
         .with_state(state);
 
-    tracing::debug!(
-        "GraphiQL IDE: http://{}:{}",
-        listener.local_addr()?.ip(),
-        listener.local_addr()?.port()
-    );
+    if config.graphiql {
+        tracing::debug!(
+            "GraphiQL IDE: http://{}:{}",
+            listener.local_addr()?.ip(),
+            listener.local_addr()?.port()
+        );
+    }
 
     let server = axum::serve(listener, app);
 
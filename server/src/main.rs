@@ -1,40 +1,66 @@
+use chrono::{Duration, Utc};
+use server::config::{Config, ConfigError};
 use server::data::{
-    create_db, create_fractal_raw, init_database, setup_example_graph, DataError, FRACTAL_ROOT_ID,
+    create_db, create_fractal_raw, get_fractal_by_id, init_database, purge_trash,
+    setup_example_graph, DataError, FRACTAL_ROOT_ID,
 };
 use server::run;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
+    // Report configuration problems before anything else is started
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(ConfigError::Cli(e)) => e.exit(),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "debug".into()),
-        )
+        .with(tracing_subscriber::EnvFilter::new(&config.log_level))
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await?;
+    let addr = config.socket_addr().map_err(std::io::Error::other)?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
 
     // Create and initialize the database
-    let db =
-        create_db("./demo_db").map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let db = create_db(&config.db_path).map_err(std::io::Error::other)?;
 
     // Create a new scope for database initialization
     {
-        let conn = server::data::create_connection(&db)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-        init_database(&conn).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-        match create_fractal_raw(&conn, "Root", None, None, Some(FRACTAL_ROOT_ID)) {
-            Ok(_) => setup_example_graph(&conn),
-            Err(DataError::FractalAlreadyExists(_)) => {
+        let conn = server::data::create_connection(&db).map_err(std::io::Error::other)?;
+        init_database(&conn).map_err(std::io::Error::other)?;
+        // Root is found by its id; a name lookup would not tell it apart from other fractals
+        match get_fractal_by_id(&conn, &FRACTAL_ROOT_ID) {
+            Ok(_) => {
                 println!("Root fractal already exists.");
                 Ok(())
             }
+            Err(DataError::FractalNotFound(_)) => {
+                create_fractal_raw(&conn, "Root", None, None, Some(FRACTAL_ROOT_ID)).and_then(
+                    |_| match config.seed_example_graph {
+                        true => setup_example_graph(&conn),
+                        false => Ok(()),
+                    },
+                )
+            }
             Err(e) => Err(e),
         }
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+        .map_err(std::io::Error::other)?;
+
+        if config.trash_retention_days > 0 {
+            let cutoff = Utc::now() - Duration::days(config.trash_retention_days.into());
+            let purged = purge_trash(&conn, cutoff).map_err(std::io::Error::other)?;
+            println!(
+                "Purged {} fractals and {} knowledge entries from the trash.",
+                purged.fractals, purged.knowledge
+            );
+        }
     } // conn is dropped here
 
-    run(listener, db)?.await
+    run(listener, db, &config)?.await
 }
@@ -0,0 +1,106 @@
+use reqwest::{Client, StatusCode};
+use server::config::{Config, ConfigError};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::utils::spawn_app_with_config;
+
+fn write_config_file(content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("fractal-config-{}.toml", Uuid::new_v4()));
+    std::fs::write(&path, content).expect("Failed to write config file");
+    path
+}
+
+#[test]
+fn test_config_flags_override_file() {
+    // Arrange
+    let path = write_config_file(
+        r#"
+        bind_address = "0.0.0.0:9000"
+        db_path = "/var/lib/fractal"
+        cors_origins = ["https://fractal.example"]
+        seed_example_graph = false
+        "#,
+    );
+
+    // Act
+    let config = Config::load_from([
+        "server",
+        "--config",
+        path.to_str().unwrap(),
+        "--db-path",
+        ":memory:",
+        "--graphiql",
+        "false",
+        "--trash-retention-days",
+        "0",
+    ])
+    .expect("Failed to load config");
+
+    // Assert
+    assert_eq!(
+        config,
+        Config {
+            bind_address: "0.0.0.0:9000".to_string(),
+            db_path: ":memory:".to_string(),
+            cors_origins: vec!["https://fractal.example".to_string()],
+            seed_example_graph: false,
+            graphiql: false,
+            trash_retention_days: 0,
+            ..Config::default()
+        }
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_invalid_config_reports_every_problem() {
+    // Act
+    let result = Config::load_from([
+        "server",
+        "--bind-address",
+        "not an address",
+        "--db-path",
+        "",
+        "--cors-origins",
+        "*,https://fractal.example",
+    ]);
+
+    // Assert
+    match result {
+        Err(ConfigError::Invalid(errors)) => assert_eq!(errors.len(), 3, "{:?}", errors),
+        other => panic!("Expected ConfigError::Invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_config_file_key_is_rejected() {
+    let path = write_config_file("bind_adress = \"127.0.0.1:8000\"");
+
+    let result = Config::load_from(["server", "--config", path.to_str().unwrap()]);
+
+    assert!(matches!(result, Err(ConfigError::Parse { .. })));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_graphiql_can_be_disabled() {
+    // Arrange
+    let address = spawn_app_with_config(Config {
+        graphiql: false,
+        ..Config::default()
+    })
+    .await;
+    let client = Client::new();
+
+    // Act
+    let response = client
+        .get(&address)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
@@ -0,0 +1,71 @@
+use server::data::{
+    create_connection, create_db, create_fractal_raw, get_fractal_by_name, init_database,
+    DataError, FRACTAL_ROOT_ID,
+};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+fn temp_db_path() -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("fractal-test-{}", Uuid::new_v4()))
+        .join("db")
+}
+
+#[test]
+fn test_database_persists_between_opens() {
+    // Arrange
+    let path = temp_db_path();
+    let path_str = path.to_str().unwrap();
+
+    {
+        let db = create_db(path_str).expect("Failed to create database");
+        let conn = create_connection(&db).expect("Failed to create connection.");
+        init_database(&conn).expect("Failed to initialize database.");
+        create_fractal_raw(&conn, "Root", None, None, Some(FRACTAL_ROOT_ID))
+            .expect("Failed to create Root fractal.");
+    } // db is dropped and flushed here
+
+    // Act
+    let db = create_db(path_str).expect("Failed to reopen database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    init_database(&conn).expect("Failed to initialize database.");
+    let root = get_fractal_by_name(&conn, "Root");
+
+    // Assert
+    assert_eq!(
+        root.expect("Root fractal was not persisted").id,
+        FRACTAL_ROOT_ID
+    );
+
+    drop(conn);
+    drop(db);
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn test_database_unavailable_reports_path() {
+    // Arrange - a regular file where the database directory should be
+    let path = temp_db_path();
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "not a database").unwrap();
+    let path_str = path.to_str().unwrap();
+
+    // Act
+    let result = create_db(path_str);
+
+    // Assert
+    match result {
+        Err(DataError::DatabaseUnavailable { path, .. }) => assert_eq!(path, path_str),
+        other => panic!("Expected DatabaseUnavailable, got {:?}", other),
+    }
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn test_empty_database_path_is_rejected() {
+    assert!(matches!(
+        create_db(""),
+        Err(DataError::DatabaseUnavailable { .. })
+    ));
+}
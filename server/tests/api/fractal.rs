@@ -1,4 +1,4 @@
-use crate::utils::spawn_app;
+use crate::utils::{create_fractal, graphql_request, spawn_app};
 use reqwest::Client;
 use serde_json::json;
 
@@ -24,7 +24,7 @@ async fn test_fractal_query() {
 
     // Act
     let response = client
-        .post(&format!("{}", &address))
+        .post(&address)
         .header("Content-Type", "application/json")
         .body(
             json!({
@@ -65,7 +65,7 @@ async fn test_create_fractal_mutation() {
     "#;
 
     let root_response = client
-        .post(&format!("{}", &address))
+        .post(&address)
         .header("Content-Type", "application/json")
         .body(json!({"query": root_query}).to_string())
         .send()
@@ -99,7 +99,7 @@ async fn test_create_fractal_mutation() {
 
     // Act
     let response = client
-        .post(&format!("{}", &address))
+        .post(&address)
         .header("Content-Type", "application/json")
         .body(
             json!({
@@ -151,7 +151,7 @@ async fn test_fractal_name_uniqueness() {
     "#;
 
     let root_response = client
-        .post(&format!("{}", &address))
+        .post(&address)
         .header("Content-Type", "application/json")
         .body(json!({"query": root_query}).to_string())
         .send()
@@ -159,7 +159,7 @@ async fn test_fractal_name_uniqueness() {
         .expect("Failed to execute root query request.");
 
     let root_body = root_response.json::<serde_json::Value>().await.unwrap();
-    let root_id = root_body["data"]["root"]["id"].as_str().unwrap();
+    let root_id = root_body["data"]["fractal"]["id"].as_str().unwrap();
 
     // GraphQL mutation
     let mutation = r#"
@@ -181,7 +181,7 @@ async fn test_fractal_name_uniqueness() {
 
     // Act - Create first fractal
     let response = client
-        .post(&format!("{}", &address))
+        .post(&address)
         .header("Content-Type", "application/json")
         .body(
             json!({
@@ -203,7 +203,7 @@ async fn test_fractal_name_uniqueness() {
 
     // Act - Attempt to create second fractal with the same name
     let response = client
-        .post(&format!("{}", &address))
+        .post(&address)
         .header("Content-Type", "application/json")
         .body(
             json!({
@@ -229,3 +229,208 @@ async fn test_fractal_name_uniqueness() {
     let error_message = errors[0]["message"].as_str().unwrap();
     assert!(error_message.contains("already exists"));
 }
+
+#[tokio::test]
+async fn test_update_fractal_mutation() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    let created = create_fractal(&client, &address, "Old Name", &root_id, vec![])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    let id = created["data"]["createFractal"]["id"].as_str().unwrap();
+    create_fractal(&client, &address, "Taken", &root_id, vec![]).await;
+
+    let fractal_query = r#"
+        query ($name: String) {
+            fractal(name: $name) {
+                updatedAt
+            }
+        }
+    "#;
+    let original = graphql_request(
+        &client,
+        &address,
+        fractal_query,
+        json!({"name": "Old Name"}),
+    )
+    .await;
+    let original_updated_at = original["data"]["fractal"]["updatedAt"].clone();
+
+    let mutation = r#"
+        mutation ($input: UpdateFractalInput!) {
+            updateFractal(input: $input) {
+                id
+                name
+                updatedAt
+            }
+        }
+    "#;
+
+    // Act - rename with the current updatedAt
+    let body = graphql_request(
+        &client,
+        &address,
+        mutation,
+        json!({"input": {"id": id, "name": "New Name", "expectedUpdatedAt": original_updated_at}}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    let updated = &body["data"]["updateFractal"];
+    assert_eq!(updated["id"], id);
+    assert_eq!(updated["name"], "New Name");
+    assert_ne!(updated["updatedAt"], original_updated_at);
+
+    // Act - a second update based on the stale updatedAt
+    let body = graphql_request(
+        &client,
+        &address,
+        mutation,
+        json!({"input": {"id": id, "name": "Newer Name", "expectedUpdatedAt": original_updated_at}}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(body["errors"][0]["extensions"]["code"], "CONFLICT");
+
+    // Act - rename onto an existing name
+    let body = graphql_request(
+        &client,
+        &address,
+        mutation,
+        json!({"input": {"id": id, "name": "Taken"}}),
+    )
+    .await;
+
+    // Assert
+    let error_message = body["errors"][0]["message"].as_str().unwrap();
+    assert!(error_message.contains("already exists"));
+}
+
+#[tokio::test]
+async fn test_fractal_lookup_by_id() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+    let missing_id = uuid::Uuid::new_v4().to_string();
+
+    let created = create_fractal(&client, &address, "By Id", &root_id, vec![])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    let id = created["data"]["createFractal"]["id"].as_str().unwrap();
+
+    let query = r#"
+        query ($id: UUID!, $ids: [UUID!]!) {
+            fractal(id: $id) {
+                id
+                name
+            }
+            fractals(ids: $ids) {
+                name
+            }
+        }
+    "#;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"id": id, "ids": [root_id, missing_id, id]}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(body["data"]["fractal"]["name"], "By Id");
+    assert_eq!(
+        body["data"]["fractals"],
+        json!([{"name": "Root"}, null, {"name": "By Id"}])
+    );
+
+    // Act - unknown id
+    let body = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"id": missing_id, "ids": []}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(body["errors"][0]["extensions"]["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_node_query_resolves_fractals_and_knowledge() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    let add_knowledge = r#"
+        mutation ($input: AddKnowledgeInput!) {
+            addKnowledge(input: $input) {
+                id
+            }
+        }
+    "#;
+    let body = graphql_request(
+        &client,
+        &address,
+        add_knowledge,
+        json!({"input": {"fractalId": root_id, "content": "Everything starts here", "context": []}}),
+    )
+    .await;
+    let knowledge_id = body["data"]["addKnowledge"]["id"].as_str().unwrap();
+
+    let query = r#"
+        query ($id: UUID!) {
+            node(id: $id) {
+                id
+                __typename
+                ... on FractalGraphQL {
+                    name
+                }
+                ... on KnowledgeGraphQL {
+                    content
+                }
+            }
+        }
+    "#;
+
+    // Act
+    let fractal = graphql_request(&client, &address, query, json!({"id": root_id})).await;
+    let knowledge = graphql_request(&client, &address, query, json!({"id": knowledge_id})).await;
+    let missing = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"id": uuid::Uuid::new_v4()}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(
+        fractal["data"]["node"],
+        json!({"id": root_id, "__typename": "FractalGraphQL", "name": "Root"})
+    );
+    assert_eq!(
+        knowledge["data"]["node"],
+        json!({
+            "id": knowledge_id,
+            "__typename": "KnowledgeGraphQL",
+            "content": "Everything starts here"
+        })
+    );
+    assert!(missing["data"]["node"].is_null());
+}
@@ -0,0 +1,195 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    add_alias, add_has_child_edge, create_connection, create_fractal, find_fractals_by_name,
+    get_aliases, get_fractal_by_name, remove_alias, resolve_path, update_fractal, DataError,
+    FractalUpdate, FRACTAL_ROOT_ID,
+};
+
+use crate::utils::{create_named, empty_db, graphql_request, spawn_app};
+
+#[test]
+fn test_aliases_are_found_by_name_lookups() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let javascript = create_fractal(&conn, "JavaScript", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let slang = create_fractal(&conn, "Slang", Some(&FRACTAL_ROOT_ID), None).unwrap();
+
+    // Act
+    add_alias(&conn, &javascript.id, "JS").unwrap();
+    add_alias(&conn, &javascript.id, "ECMAScript").unwrap();
+    add_alias(&conn, &javascript.id, "JS").unwrap();
+    let by_alias = get_fractal_by_name(&conn, "ECMAScript").unwrap();
+    let by_path = resolve_path(&conn, "JS").unwrap();
+    let js = create_fractal(&conn, "JS", Some(&slang.id), None).unwrap();
+    let exact = get_fractal_by_name(&conn, "JS").unwrap();
+    let candidates = find_fractals_by_name(&conn, "JS").unwrap();
+    remove_alias(&conn, &javascript.id, "ECMAScript").unwrap();
+    let removed = get_fractal_by_name(&conn, "ECMAScript");
+
+    // Assert
+    assert_eq!(by_alias.id, javascript.id);
+    assert_eq!(by_path.fractals[1].id, javascript.id);
+    assert_eq!(exact.id, js.id);
+    assert_eq!(
+        candidates
+            .iter()
+            .map(|c| (c.fractal.id, c.matched_alias.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![(js.id, None), (javascript.id, Some("JS"))]
+    );
+    assert_eq!(get_aliases(&conn, &javascript.id).unwrap(), vec!["JS"]);
+    assert!(
+        matches!(removed, Err(DataError::FractalNotFound(_))),
+        "{:?}",
+        removed
+    );
+}
+
+#[test]
+fn test_aliases_take_names_next_to_them() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let languages = create_fractal(&conn, "Languages", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let javascript = create_fractal(&conn, "JavaScript", Some(&languages.id), None).unwrap();
+    let python = create_fractal(&conn, "Python", Some(&languages.id), None).unwrap();
+    let typescript = create_fractal(&conn, "TypeScript", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    add_alias(&conn, &javascript.id, "JS").unwrap();
+    add_alias(&conn, &typescript.id, "JS").unwrap();
+
+    // Act
+    let named_like_alias = create_fractal(&conn, "JS", Some(&languages.id), None);
+    let alias_of_sibling = add_alias(&conn, &python.id, "JavaScript");
+    let linked = add_has_child_edge(&conn, &languages.id, &typescript.id, None);
+    let elsewhere = create_fractal(&conn, "JS", Some(&python.id), None);
+
+    // Assert
+    assert!(
+        matches!(named_like_alias, Err(DataError::FractalAlreadyExists(ref name)) if name == "JS"),
+        "{:?}",
+        named_like_alias
+    );
+    assert!(
+        matches!(alias_of_sibling, Err(DataError::FractalAlreadyExists(ref name)) if name == "JavaScript"),
+        "{:?}",
+        alias_of_sibling
+    );
+    assert!(
+        matches!(linked, Err(DataError::FractalAlreadyExists(ref name)) if name == "JS"),
+        "{:?}",
+        linked
+    );
+    assert!(elsewhere.is_ok(), "{:?}", elsewhere);
+}
+
+#[test]
+fn test_invalid_aliases_are_rejected_and_renaming_to_an_alias_drops_it() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let javascript = create_fractal(&conn, "JavaScript", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    add_alias(&conn, &javascript.id, "JS").unwrap();
+    add_alias(&conn, &javascript.id, "ECMAScript").unwrap();
+
+    // Act
+    let empty = add_alias(&conn, &javascript.id, "  ");
+    let with_separator = add_alias(&conn, &javascript.id, "Web/JS");
+    let renamed = update_fractal(
+        &conn,
+        &javascript.id,
+        &FractalUpdate {
+            name: Some("ECMAScript".to_string()),
+        },
+        None,
+    )
+    .unwrap();
+
+    // Assert
+    assert!(
+        matches!(empty, Err(DataError::InvalidAlias(_))),
+        "{:?}",
+        empty
+    );
+    assert!(
+        matches!(with_separator, Err(DataError::InvalidAlias(_))),
+        "{:?}",
+        with_separator
+    );
+    assert_eq!(renamed.name, "ECMAScript");
+    assert_eq!(get_aliases(&conn, &javascript.id).unwrap(), vec!["JS"]);
+}
+
+#[tokio::test]
+async fn test_alias_mutations_and_matched_alias() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let id = create_named(
+        &client,
+        &address,
+        "JavaScript",
+        &FRACTAL_ROOT_ID.to_string(),
+    )
+    .await;
+    let lookup = r#"
+        query ($name: String) {
+            fractal(name: $name) { id aliases matchedAlias }
+            fractalsByName(name: $name) { fractal { id } matchedAlias }
+        }
+    "#;
+
+    // Act
+    let added = graphql_request(
+        &client,
+        &address,
+        r#"mutation ($id: UUID!) { addAlias(id: $id, alias: "JS") { aliases } }"#,
+        json!({"id": id}),
+    )
+    .await;
+    let by_alias = graphql_request(&client, &address, lookup, json!({"name": "JS"})).await;
+    let by_name = graphql_request(&client, &address, lookup, json!({"name": "JavaScript"})).await;
+    let removed = graphql_request(
+        &client,
+        &address,
+        r#"mutation ($id: UUID!) { removeAlias(id: $id, alias: "JS") { aliases } }"#,
+        json!({"id": id}),
+    )
+    .await;
+    let gone = graphql_request(&client, &address, lookup, json!({"name": "JS"})).await;
+    let invalid = graphql_request(
+        &client,
+        &address,
+        r#"mutation ($id: UUID!) { addAlias(id: $id, alias: "") { aliases } }"#,
+        json!({"id": id}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(
+        added["data"]["addAlias"]["aliases"],
+        json!(["JS"]),
+        "{:?}",
+        added
+    );
+    assert_eq!(
+        by_alias["data"],
+        json!({
+            "fractal": {"id": id, "aliases": ["JS"], "matchedAlias": "JS"},
+            "fractalsByName": [{"fractal": {"id": id}, "matchedAlias": "JS"}],
+        })
+    );
+    assert_eq!(by_name["data"]["fractal"]["matchedAlias"], json!(null));
+    assert_eq!(removed["data"]["removeAlias"]["aliases"], json!([]));
+    assert_eq!(
+        gone["errors"][0]["extensions"]["code"], "NOT_FOUND",
+        "{:?}",
+        gone
+    );
+    assert_eq!(
+        invalid["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        invalid
+    );
+}
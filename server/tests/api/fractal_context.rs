@@ -3,7 +3,7 @@ use reqwest::Client;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::utils::{create_fractal, spawn_app};
+use crate::utils::{create_fractal, create_named, graphql_request, spawn_app};
 
 #[tokio::test]
 async fn test_fractal_context_mutation() {
@@ -58,8 +58,7 @@ async fn test_fractal_context_mutation() {
         "String",
         programing_body["data"]["createFractal"]["id"]
             .as_str()
-            .unwrap_or("")
-            .into(),
+            .unwrap_or(""),
         vec![],
     )
     .await;
@@ -92,3 +91,530 @@ async fn test_fractal_context_mutation() {
         }),
     );
 }
+
+const CREATE_WITH_PARENTS: &str = r#"
+    mutation ($input: CreateFractalInput!) {
+        createFractal(input: $input) {
+            id
+            parents {
+                name
+            }
+        }
+    }
+"#;
+
+#[tokio::test]
+async fn test_create_fractal_under_several_parents_with_contexts() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let python = create_named(&client, &address, "Python", &programming).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        CREATE_WITH_PARENTS,
+        json!({"input": {
+            "name": "String",
+            "parents": [
+                {"parentId": programming},
+                {"parentId": python, "contextId": programming},
+                {"parentId": rust, "contextId": programming},
+            ],
+        }}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    let mut parents = body["data"]["createFractal"]["parents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    parents.sort();
+    assert_eq!(parents, vec!["Programming", "Python", "Rust"]);
+
+    let children = graphql_request(
+        &client,
+        &address,
+        r#"
+            query ($contextId: UUID) {
+                fractal(name: "Rust") {
+                    children(input: {contextId: $contextId}) {
+                        name
+                    }
+                }
+            }
+        "#,
+        json!({"contextId": programming}),
+    )
+    .await;
+    assert_eq!(
+        children["data"]["fractal"]["children"],
+        json!([{"name": "String"}])
+    );
+}
+
+#[tokio::test]
+async fn test_create_fractal_uses_every_context_id() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let text = create_named(&client, &address, "Text", &root_id).await;
+
+    // Act
+    let body = create_fractal(
+        &client,
+        &address,
+        "String",
+        &root_id,
+        vec![&programming, &text],
+    )
+    .await
+    .json::<serde_json::Value>()
+    .await
+    .unwrap();
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    for context_id in [&programming, &text] {
+        let children = graphql_request(
+            &client,
+            &address,
+            "query ($contextId: UUID) { fractal { children(input: {contextId: $contextId}) { name } } }",
+            json!({"contextId": context_id}),
+        )
+        .await;
+        assert_eq!(
+            children["data"]["fractal"]["children"],
+            json!([{"name": "String"}])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_create_fractal_reports_missing_parents_and_contexts() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let missing_parent = Uuid::new_v4();
+    let missing_context = Uuid::new_v4();
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        CREATE_WITH_PARENTS,
+        json!({"input": {
+            "name": "Orphan",
+            "parents": [
+                {"parentId": root_id},
+                {"parentId": missing_parent, "contextId": missing_context},
+            ],
+        }}),
+    )
+    .await;
+
+    // Assert
+    let error = &body["errors"][0];
+    assert_eq!(error["extensions"]["code"], "NOT_FOUND", "{:?}", body);
+    let message = error["message"].as_str().unwrap();
+    assert!(message.contains(&missing_parent.to_string()), "{}", message);
+    assert!(
+        message.contains(&missing_context.to_string()),
+        "{}",
+        message
+    );
+
+    let lookup = graphql_request(
+        &client,
+        &address,
+        r#"query { fractal(name: "Orphan") { id } }"#,
+        json!({}),
+    )
+    .await;
+    assert_eq!(lookup["errors"][0]["extensions"]["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_relations_can_be_recontextualized_and_removed() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+    let string = create_fractal(&client, &address, "String", &rust, vec![&programming])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap()["data"]["createFractal"]["id"]
+        .clone();
+
+    let update = r#"
+        mutation ($parentId: UUID!, $childId: UUID!, $contextId: UUID, $newContextId: UUID) {
+            updateRelationContext(
+                parentId: $parentId
+                childId: $childId
+                contextId: $contextId
+                newContextId: $newContextId
+            )
+        }
+    "#;
+    let remove = r#"
+        mutation ($parentId: UUID!, $childId: UUID!, $contextId: UUID) {
+            removeRelation(parentId: $parentId, childId: $childId, contextId: $contextId)
+        }
+    "#;
+    let children = r#"
+        query ($contextId: UUID) {
+            fractal(name: "Rust") {
+                children(input: {contextId: $contextId}) {
+                    name
+                }
+            }
+        }
+    "#;
+
+    // Act
+    let updated = graphql_request(
+        &client,
+        &address,
+        update,
+        json!({"parentId": rust, "childId": string, "contextId": programming}),
+    )
+    .await;
+    let in_programming = graphql_request(
+        &client,
+        &address,
+        children,
+        json!({"contextId": programming}),
+    )
+    .await;
+    let without_context = graphql_request(&client, &address, children, json!({})).await;
+    let removed_wrong_context = graphql_request(
+        &client,
+        &address,
+        remove,
+        json!({"parentId": rust, "childId": string, "contextId": programming}),
+    )
+    .await;
+    let removed = graphql_request(
+        &client,
+        &address,
+        remove,
+        json!({"parentId": rust, "childId": string}),
+    )
+    .await;
+    let after_removal = graphql_request(&client, &address, children, json!({})).await;
+
+    // Assert
+    assert_eq!(
+        updated["data"]["updateRelationContext"], true,
+        "{:?}",
+        updated
+    );
+    assert_eq!(in_programming["data"]["fractal"]["children"], json!([]));
+    assert_eq!(
+        without_context["data"]["fractal"]["children"],
+        json!([{"name": "String"}])
+    );
+    assert_eq!(removed_wrong_context["data"]["removeRelation"], false);
+    assert_eq!(removed["data"]["removeRelation"], true);
+    assert_eq!(after_removal["data"]["fractal"]["children"], json!([]));
+}
+
+const ADD_RELATION: &str = r#"
+    mutation ($parentId: UUID!, $childId: UUID!, $contextId: UUID) {
+        addRelation(parentId: $parentId, childId: $childId, contextId: $contextId)
+    }
+"#;
+
+#[tokio::test]
+async fn test_add_relation_ignores_duplicate_links() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let string = create_named(&client, &address, "String", &root_id).await;
+    let variables = json!({"parentId": programming, "childId": string});
+
+    // Act
+    let first = graphql_request(&client, &address, ADD_RELATION, variables.clone()).await;
+    let second = graphql_request(&client, &address, ADD_RELATION, variables).await;
+    let children = graphql_request(
+        &client,
+        &address,
+        r#"query { fractal(name: "Programming") { children { name } } }"#,
+        json!({}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(first["data"]["addRelation"], true, "{:?}", first);
+    assert_eq!(second["data"]["addRelation"], false, "{:?}", second);
+    assert_eq!(
+        children["data"]["fractal"]["children"],
+        json!([{"name": "String"}])
+    );
+}
+
+#[tokio::test]
+async fn test_add_relation_rejects_self_loops_and_cycles() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let text = create_named(&client, &address, "Text", &root_id).await;
+    let a = create_named(&client, &address, "A", &root_id).await;
+    let b = create_fractal(&client, &address, "B", &a, vec![&programming])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap()["data"]["createFractal"]["id"]
+        .clone();
+
+    // Act
+    let self_loop = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": a, "childId": a}),
+    )
+    .await;
+    let cycle_in_context = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": b, "childId": a, "contextId": programming}),
+    )
+    .await;
+    let cycle_without_context = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": b, "childId": a}),
+    )
+    .await;
+    let other_context = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": b, "childId": a, "contextId": text}),
+    )
+    .await;
+    // A -> B only holds in Programming and B -> C only in Text, so C is no ancestor of A
+    let c = create_fractal(&client, &address, "C", b.as_str().unwrap(), vec![&text])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap()["data"]["createFractal"]["id"]
+        .clone();
+    let mixed_contexts = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": c, "childId": a}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(
+        self_loop["errors"][0]["extensions"]["code"], "SELF_RELATION",
+        "{:?}",
+        self_loop
+    );
+    for body in [cycle_in_context, cycle_without_context] {
+        assert_eq!(
+            body["errors"][0]["extensions"]["code"], "RELATION_CYCLE",
+            "{:?}",
+            body
+        );
+    }
+    for body in [other_context, mixed_contexts] {
+        assert_eq!(body["data"]["addRelation"], true, "{:?}", body);
+    }
+}
+
+#[tokio::test]
+async fn test_writes_referencing_unknown_fractals_are_not_found() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let string = create_named(&client, &address, "String", &root_id).await;
+    let missing = Uuid::new_v4();
+
+    // Act
+    let unknown_parent = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": missing, "childId": string}),
+    )
+    .await;
+    let unknown_child = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": root_id, "childId": missing}),
+    )
+    .await;
+    let unknown_context = graphql_request(
+        &client,
+        &address,
+        ADD_RELATION,
+        json!({"parentId": root_id, "childId": string, "contextId": missing}),
+    )
+    .await;
+    let unknown_knowledge_owner = graphql_request(
+        &client,
+        &address,
+        "mutation ($input: AddKnowledgeInput!) { addKnowledge(input: $input) { id } }",
+        json!({"input": {"fractalId": missing, "content": "lost", "context": []}}),
+    )
+    .await;
+    let unknown_knowledge_context = graphql_request(
+        &client,
+        &address,
+        "mutation ($input: AddKnowledgeInput!) { addKnowledge(input: $input) { id } }",
+        json!({"input": {"fractalId": string, "content": "lost", "context": [missing]}}),
+    )
+    .await;
+
+    // Assert
+    for body in [
+        unknown_parent,
+        unknown_child,
+        unknown_context,
+        unknown_knowledge_owner,
+        unknown_knowledge_context,
+    ] {
+        let error = &body["errors"][0];
+        assert_eq!(error["extensions"]["code"], "NOT_FOUND", "{:?}", body);
+        assert!(
+            error["message"]
+                .as_str()
+                .unwrap()
+                .contains(&missing.to_string()),
+            "{:?}",
+            body
+        );
+    }
+
+    let string_state = graphql_request(
+        &client,
+        &address,
+        r#"query { fractal(name: "String") { parents { name } knowledges { edges { cursor } } } }"#,
+        json!({}),
+    )
+    .await;
+    assert_eq!(
+        string_state["data"]["fractal"]["parents"],
+        json!([{"name": "Root"}])
+    );
+    assert_eq!(
+        string_state["data"]["fractal"]["knowledges"]["edges"],
+        json!([])
+    );
+}
+
+#[tokio::test]
+async fn test_contexts_can_be_added_removed_and_set_on_creation() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let text = create_named(&client, &address, "Text", &root_id).await;
+    let created = graphql_request(
+        &client,
+        &address,
+        "mutation ($input: CreateFractalInput!) { createFractal(input: $input) { id contexts { name } } }",
+        json!({"input": {"name": "String", "parentId": root_id, "contexts": [programming]}}),
+    )
+    .await;
+    let string = created["data"]["createFractal"]["id"].clone();
+    let add = "mutation ($fractalId: UUID!, $contextId: UUID!) { addContext(fractalId: $fractalId, contextId: $contextId) }";
+    let remove = "mutation ($fractalId: UUID!, $contextId: UUID!) { removeContext(fractalId: $fractalId, contextId: $contextId) }";
+    let state = r#"
+        query {
+            string: fractal(name: "String") { contexts { name } }
+            programming: fractal(name: "Programming") { providesContextFor { name } }
+        }
+    "#;
+
+    // Act
+    let added = graphql_request(
+        &client,
+        &address,
+        add,
+        json!({"fractalId": string, "contextId": text}),
+    )
+    .await;
+    let added_again = graphql_request(
+        &client,
+        &address,
+        add,
+        json!({"fractalId": string, "contextId": text}),
+    )
+    .await;
+    let with_both = graphql_request(&client, &address, state, json!({})).await;
+    let removed = graphql_request(
+        &client,
+        &address,
+        remove,
+        json!({"fractalId": string, "contextId": programming}),
+    )
+    .await;
+    let after_removal = graphql_request(&client, &address, state, json!({})).await;
+
+    // Assert
+    assert!(created.get("errors").is_none(), "{:?}", created);
+    assert_eq!(
+        created["data"]["createFractal"]["contexts"],
+        json!([{"name": "Programming"}])
+    );
+    assert_eq!(added["data"]["addContext"], true, "{:?}", added);
+    assert_eq!(added_again["data"]["addContext"], false);
+
+    let mut contexts = with_both["data"]["string"]["contexts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    contexts.sort();
+    assert_eq!(contexts, vec!["Programming", "Text"]);
+    assert_eq!(
+        with_both["data"]["programming"]["providesContextFor"],
+        json!([{"name": "String"}])
+    );
+
+    assert_eq!(removed["data"]["removeContext"], true, "{:?}", removed);
+    assert_eq!(
+        after_removal["data"]["string"]["contexts"],
+        json!([{"name": "Text"}])
+    );
+    assert_eq!(
+        after_removal["data"]["programming"]["providesContextFor"],
+        json!([])
+    );
+}
@@ -0,0 +1,222 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    create_connection, create_fractal, delete_fractal, get_fractal_by_id, DataError, DeleteMode,
+    FRACTAL_ROOT_ID, MAX_HIERARCHY_DEPTH,
+};
+use uuid::Uuid;
+
+use crate::utils::{create_named, empty_db, graphql_request, spawn_app};
+
+const DELETE_FRACTAL: &str = r#"
+    mutation ($id: UUID!, $mode: DeleteMode, $dryRun: Boolean) {
+        deleteFractal(id: $id, mode: $mode, dryRun: $dryRun) {
+            dryRun
+            deletedFractals {
+                name
+            }
+            deletedKnowledge {
+                content
+            }
+            reparentedFractals {
+                name
+            }
+        }
+    }
+"#;
+
+async fn parent_names(client: &Client, address: &str, name: &str) -> serde_json::Value {
+    let body = graphql_request(
+        client,
+        address,
+        "query ($name: String) { fractal(name: $name) { parents { name } } }",
+        json!({"name": name}),
+    )
+    .await;
+    body["data"]["fractal"]["parents"].clone()
+}
+
+#[tokio::test]
+async fn test_root_cannot_be_deleted() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": Uuid::nil(), "mode": "CASCADE"}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(
+        body["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_delete_refuses_fractals_with_children_by_default() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+
+    // Act
+    let refused = graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": programming}),
+    )
+    .await;
+    let leaf = graphql_request(&client, &address, DELETE_FRACTAL, json!({"id": rust})).await;
+
+    // Assert
+    assert_eq!(
+        refused["errors"][0]["extensions"]["code"], "CONFLICT",
+        "{:?}",
+        refused
+    );
+    assert!(leaf.get("errors").is_none(), "{:?}", leaf);
+    assert_eq!(
+        leaf["data"]["deleteFractal"]["deletedFractals"],
+        json!([{"name": "Rust"}])
+    );
+    assert_eq!(
+        parent_names(&client, &address, "Programming").await,
+        json!([{"name": "Root"}])
+    );
+}
+
+#[tokio::test]
+async fn test_delete_with_reparent_moves_children_up() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    create_named(&client, &address, "Rust", &programming).await;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": programming, "mode": "REPARENT"}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["deleteFractal"]["reparentedFractals"],
+        json!([{"name": "Rust"}])
+    );
+    assert_eq!(
+        parent_names(&client, &address, "Rust").await,
+        json!([{"name": "Root"}])
+    );
+}
+
+#[tokio::test]
+async fn test_cascade_deletes_only_the_exclusive_subtree() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+    create_named(&client, &address, "Ownership", &rust).await;
+    let string = create_named(&client, &address, "String", &programming).await;
+    let text = create_named(&client, &address, "Text", &root_id).await;
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($parentId: UUID!, $childId: UUID!) { addRelation(parentId: $parentId, childId: $childId) }",
+        json!({"parentId": text, "childId": string}),
+    )
+    .await;
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($input: AddKnowledgeInput!) { addKnowledge(input: $input) { id } }",
+        json!({"input": {"fractalId": rust, "content": "Memory safe", "context": []}}),
+    )
+    .await;
+    let variables = json!({"id": programming, "mode": "CASCADE", "dryRun": true});
+
+    // Act
+    let dry_run = graphql_request(&client, &address, DELETE_FRACTAL, variables).await;
+    let still_there = parent_names(&client, &address, "Ownership").await;
+    let deleted = graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": programming, "mode": "CASCADE"}),
+    )
+    .await;
+
+    // Assert
+    assert!(dry_run.get("errors").is_none(), "{:?}", dry_run);
+    let expected = json!({
+        "deletedFractals": [{"name": "Programming"}, {"name": "Rust"}, {"name": "Ownership"}],
+        "deletedKnowledge": [{"content": "Memory safe"}],
+        "reparentedFractals": [],
+    });
+    assert_eq!(dry_run["data"]["deleteFractal"]["dryRun"], true);
+    assert_eq!(still_there, json!([{"name": "Rust"}]));
+
+    assert!(deleted.get("errors").is_none(), "{:?}", deleted);
+    assert_eq!(deleted["data"]["deleteFractal"]["dryRun"], false);
+    for body in [&dry_run, &deleted] {
+        let report = &body["data"]["deleteFractal"];
+        for field in ["deletedFractals", "deletedKnowledge", "reparentedFractals"] {
+            assert_eq!(report[field], expected[field], "{}", field);
+        }
+    }
+
+    assert_eq!(
+        parent_names(&client, &address, "String").await,
+        json!([{"name": "Text"}])
+    );
+    let lookup = graphql_request(
+        &client,
+        &address,
+        r#"query { fractal(name: "Ownership") { id } }"#,
+        json!({}),
+    )
+    .await;
+    assert_eq!(lookup["errors"][0]["extensions"]["code"], "NOT_FOUND");
+}
+
+#[test]
+fn test_cascade_reaches_below_the_hierarchy_depth_limit() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let top = create_fractal(&conn, "Level 0", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let mut parent_id = top.id;
+    for level in 1..=MAX_HIERARCHY_DEPTH + 2 {
+        parent_id = create_fractal(&conn, &format!("Level {}", level), Some(&parent_id), None)
+            .unwrap()
+            .id;
+    }
+
+    // Act
+    let report = delete_fractal(&conn, &top.id, DeleteMode::Cascade, false, None).unwrap();
+
+    // Assert
+    assert_eq!(report.fractals.len(), MAX_HIERARCHY_DEPTH + 3);
+    assert_eq!(report.fractals.last().unwrap().id, parent_id);
+    assert!(matches!(
+        get_fractal_by_id(&conn, &parent_id),
+        Err(DataError::FractalNotFound(_))
+    ));
+}
@@ -0,0 +1,184 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    add_has_child_edge, create_connection, get_ancestors, get_descendants, HierarchyEntry,
+    FRACTAL_ROOT_ID, MAX_HIERARCHY_DEPTH,
+};
+
+use crate::utils::{create_fractal, empty_db, example_db, graphql_request, id_of, spawn_app};
+
+fn names_and_depths(entries: &[HierarchyEntry]) -> Vec<(&str, usize)> {
+    entries
+        .iter()
+        .map(|e| (e.fractal.name.as_str(), e.depth))
+        .collect()
+}
+
+#[test]
+fn test_ancestors_list_every_link_up_to_root() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let amp_str = id_of(&conn, "&str");
+
+    // Act
+    let all = get_ancestors(&conn, &amp_str, MAX_HIERARCHY_DEPTH).unwrap();
+    let parents = get_ancestors(&conn, &amp_str, 1).unwrap();
+
+    // Assert
+    assert_eq!(
+        names_and_depths(&all),
+        vec![
+            ("String", 1),
+            ("Programming", 2),
+            ("Python", 2),
+            ("Rust", 2),
+            ("Programming", 3),
+            ("Programming", 3),
+            ("Root", 3),
+        ]
+    );
+    assert_eq!(names_and_depths(&parents), vec![("String", 1)]);
+    assert_eq!(parents[0].edge.child_id, amp_str);
+    assert_eq!(parents[0].edge.context_id, Some(id_of(&conn, "Rust")));
+}
+
+#[test]
+fn test_descendants_only_follow_links_in_the_requested_context() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let rust = id_of(&conn, "Rust");
+    let programming = id_of(&conn, "Programming");
+
+    // Act
+    let in_programming =
+        get_descendants(&conn, &rust, MAX_HIERARCHY_DEPTH, Some(&programming)).unwrap();
+    let direct = get_descendants(&conn, &programming, 1, None).unwrap();
+
+    // Assert
+    assert_eq!(
+        names_and_depths(&in_programming),
+        vec![("Ownership", 1), ("String", 1), ("String literal", 2)]
+    );
+    assert!(in_programming
+        .iter()
+        .all(|e| e.edge.context_id == Some(programming)));
+    assert_eq!(
+        names_and_depths(&direct),
+        vec![("C", 1), ("Python", 1), ("Rust", 1), ("String", 1)]
+    );
+}
+
+#[test]
+fn test_descendants_of_stacked_diamonds_get_their_shortest_depth() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let create = |name: String, parent_id| {
+        server::data::create_fractal(&conn, &name, Some(&parent_id), None)
+            .unwrap()
+            .id
+    };
+    let top = create("Top".to_string(), FRACTAL_ROOT_ID);
+    let diamonds = MAX_HIERARCHY_DEPTH / 2;
+    let mut join = top;
+    for i in 1..=diamonds {
+        let left = create(format!("Left {}", i), join);
+        let right = create(format!("Right {}", i), join);
+        join = create(format!("Join {}", i), left);
+        add_has_child_edge(&conn, &right, &join, None).unwrap();
+    }
+
+    // Act
+    let descendants = get_descendants(&conn, &top, MAX_HIERARCHY_DEPTH, None).unwrap();
+
+    // Assert
+    assert_eq!(descendants.len(), 4 * diamonds);
+    let last = descendants.last().unwrap();
+    assert_eq!(last.fractal.id, join);
+    assert_eq!(last.depth, 2 * diamonds);
+}
+
+#[tokio::test]
+async fn test_descendants_load_a_subtree_in_one_request() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = FRACTAL_ROOT_ID.to_string();
+    let mut parent_id = root_id.clone();
+    let mut ids = Vec::new();
+    for name in ["Programming", "Rust", "Ownership"] {
+        let body = create_fractal(&client, &address, name, &parent_id, vec![])
+            .await
+            .json::<serde_json::Value>()
+            .await
+            .unwrap();
+        parent_id = body["data"]["createFractal"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        ids.push(parent_id.clone());
+    }
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        r#"
+            query {
+                fractal {
+                    descendants(maxDepth: 2) {
+                        depth
+                        fractal { name }
+                        relation { parentId childId contextId }
+                    }
+                }
+            }
+        "#,
+        json!({}),
+    )
+    .await;
+    let negative = graphql_request(
+        &client,
+        &address,
+        "query { fractal { ancestors(maxDepth: -1) { depth } } }",
+        json!({}),
+    )
+    .await;
+    let too_deep = graphql_request(
+        &client,
+        &address,
+        "query ($maxDepth: Int) { fractal { descendants(maxDepth: $maxDepth) { depth } } }",
+        json!({"maxDepth": MAX_HIERARCHY_DEPTH + 1}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["fractal"]["descendants"],
+        json!([
+            {
+                "depth": 1,
+                "fractal": {"name": "Programming"},
+                "relation": {"parentId": root_id, "childId": ids[0], "contextId": null},
+            },
+            {
+                "depth": 2,
+                "fractal": {"name": "Rust"},
+                "relation": {"parentId": ids[0], "childId": ids[1], "contextId": null},
+            },
+        ])
+    );
+    assert_eq!(
+        negative["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        negative
+    );
+    assert_eq!(
+        too_deep["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        too_deep
+    );
+}
@@ -0,0 +1,295 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    copy_subtree, create_connection, create_fractal, CopyNaming, DataError, FRACTAL_ROOT_ID,
+    MAX_COPY_NUMBER,
+};
+use uuid::Uuid;
+
+use crate::utils::{create_named, empty_db, graphql_request, spawn_app};
+
+const MOVE_FRACTAL: &str = r#"
+    mutation ($id: UUID!, $from: UUID!, $to: UUID!, $contextId: UUID) {
+        moveFractal(id: $id, fromParentId: $from, toParentId: $to, contextId: $contextId) {
+            name
+            parents { name }
+        }
+    }
+"#;
+
+const COPY_SUBTREE: &str = r#"
+    mutation ($id: UUID!, $to: UUID!, $renames: [RenameInput!], $suffix: String) {
+        copySubtree(id: $id, toParentId: $to, renames: $renames, suffix: $suffix) {
+            id
+            name
+        }
+    }
+"#;
+
+async fn child_names(client: &Client, address: &str, name: &str) -> serde_json::Value {
+    let body = graphql_request(
+        client,
+        address,
+        "query ($name: String) { fractal(name: $name) { children { name } } }",
+        json!({"name": name}),
+    )
+    .await;
+    body["data"]["fractal"]["children"].clone()
+}
+
+#[tokio::test]
+async fn test_move_fractal_rewires_its_parent() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let languages = create_named(&client, &address, "Languages", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        MOVE_FRACTAL,
+        json!({"id": rust, "from": programming, "to": languages}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["moveFractal"],
+        json!({"name": "Rust", "parents": [{"name": "Languages"}]})
+    );
+    assert_eq!(
+        child_names(&client, &address, "Programming").await,
+        json!([])
+    );
+}
+
+#[tokio::test]
+async fn test_move_fractal_under_its_descendant_changes_nothing() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+
+    // Act
+    let cycle = graphql_request(
+        &client,
+        &address,
+        MOVE_FRACTAL,
+        json!({"id": programming, "from": root_id, "to": rust}),
+    )
+    .await;
+    let not_a_child = graphql_request(
+        &client,
+        &address,
+        MOVE_FRACTAL,
+        json!({"id": rust, "from": root_id, "to": programming}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(
+        cycle["errors"][0]["extensions"]["code"], "RELATION_CYCLE",
+        "{:?}",
+        cycle
+    );
+    assert_eq!(
+        not_a_child["errors"][0]["extensions"]["code"], "NOT_FOUND",
+        "{:?}",
+        not_a_child
+    );
+    assert_eq!(
+        child_names(&client, &address, "Root").await,
+        json!([{"name": "Programming"}])
+    );
+}
+
+#[tokio::test]
+async fn test_copy_subtree_duplicates_descendants_and_knowledge() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let rust = create_named(&client, &address, "Rust", &root_id).await;
+    let string = create_named(&client, &address, "String", &rust).await;
+    let archive = create_named(&client, &address, "Archive", &root_id).await;
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($input: AddKnowledgeInput!) { addKnowledge(input: $input) { id } }",
+        json!({"input": {"fractalId": string, "content": "Owned UTF-8 text", "context": [rust]}}),
+    )
+    .await;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        COPY_SUBTREE,
+        json!({"id": rust, "to": archive}),
+    )
+    .await;
+    let knowledge = graphql_request(
+        &client,
+        &address,
+        r#"
+            query {
+                knowledge(fractalName: "String (copy)", context: []) {
+                    content
+                    contexts { name }
+                }
+            }
+        "#,
+        json!({}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    let names = body["data"]["copySubtree"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Rust (copy)", "String (copy)"]);
+    assert_eq!(
+        child_names(&client, &address, "Archive").await,
+        json!([{"name": "Rust (copy)"}])
+    );
+    assert_eq!(
+        child_names(&client, &address, "Rust (copy)").await,
+        json!([{"name": "String (copy)"}])
+    );
+    assert_eq!(
+        child_names(&client, &address, "Rust").await,
+        json!([{"name": "String"}])
+    );
+    assert_eq!(
+        knowledge["data"]["knowledge"],
+        json!([{"content": "Owned UTF-8 text", "contexts": [{"name": "Rust (copy)"}]}])
+    );
+}
+
+#[tokio::test]
+async fn test_copy_subtree_applies_renames_and_numbers_taken_names() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let rust = create_named(&client, &address, "Rust", &root_id).await;
+    create_named(&client, &address, "String", &rust).await;
+    create_named(&client, &address, "Rust v2", &root_id).await;
+
+    // Act
+    let numbered = graphql_request(
+        &client,
+        &address,
+        COPY_SUBTREE,
+        json!({"id": rust, "to": root_id, "suffix": " v2"}),
+    )
+    .await;
+    let renamed = graphql_request(
+        &client,
+        &address,
+        COPY_SUBTREE,
+        json!({"id": rust, "to": root_id, "renames": [{"id": rust, "to": "Rust 2021"}]}),
+    )
+    .await;
+    let taken = graphql_request(
+        &client,
+        &address,
+        COPY_SUBTREE,
+        json!({"id": rust, "to": root_id, "renames": [{"id": rust, "to": "Rust v2"}]}),
+    )
+    .await;
+
+    // Assert
+    let names = |body: &serde_json::Value| {
+        body["data"]["copySubtree"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert!(numbered.get("errors").is_none(), "{:?}", numbered);
+    // Only the copy of Rust joins existing siblings, String is alone under it
+    assert_eq!(names(&numbered), vec!["Rust v2 2", "String v2"]);
+    assert_eq!(names(&renamed), vec!["Rust 2021", "String (copy)"]);
+    assert_eq!(
+        taken["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        taken
+    );
+}
+
+#[tokio::test]
+async fn test_copy_subtree_renames_only_the_fractal_with_the_given_id() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let rust = create_named(&client, &address, "Rust", &root_id).await;
+    create_named(&client, &address, "String", &rust).await;
+    let collections = create_named(&client, &address, "Collections", &rust).await;
+    let string = create_named(&client, &address, "String", &collections).await;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        COPY_SUBTREE,
+        json!({"id": rust, "to": root_id, "renames": [{"id": string, "to": "Text"}]}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    let mut names = body["data"]["copySubtree"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["Collections (copy)", "Rust (copy)", "String (copy)", "Text"]
+    );
+}
+
+#[test]
+fn test_copy_subtree_gives_up_when_every_numbered_name_is_taken() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let rust = create_fractal(&conn, "Rust", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    create_fractal(&conn, "Rust (copy)", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    for n in 2..=MAX_COPY_NUMBER {
+        let name = format!("Rust (copy) {}", n);
+        create_fractal(&conn, &name, Some(&FRACTAL_ROOT_ID), None).unwrap();
+    }
+
+    // Act
+    let result = copy_subtree(
+        &conn,
+        &rust.id,
+        &FRACTAL_ROOT_ID,
+        None,
+        &CopyNaming::default(),
+    );
+
+    // Assert
+    assert!(
+        matches!(result, Err(DataError::FractalAlreadyExists(ref name)) if name == "Rust (copy)"),
+        "{:?}",
+        result
+    );
+}
@@ -0,0 +1,185 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    add_has_child_edge, create_connection, create_fractal, find_fractals_by_name,
+    get_fractal_by_name, update_fractal, DataError, FractalUpdate, FRACTAL_ROOT_ID,
+};
+
+use crate::utils::{create_named, empty_db, graphql_request, spawn_app};
+
+#[test]
+fn test_names_are_unique_per_parent_and_context() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let python = create_fractal(&conn, "Python", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let rust = create_fractal(&conn, "Rust", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let python_string = create_fractal(&conn, "String", Some(&python.id), None).unwrap();
+
+    // Act
+    let rust_string = create_fractal(&conn, "String", Some(&rust.id), None);
+    let in_context = create_fractal(&conn, "String", Some(&python.id), Some(&rust.id));
+    let sibling = create_fractal(&conn, "String", Some(&python.id), None);
+    let relinked = add_has_child_edge(&conn, &python.id, &rust_string.as_ref().unwrap().id, None);
+    let bytes = create_fractal(&conn, "Bytes", Some(&python.id), None).unwrap();
+    let renamed = update_fractal(
+        &conn,
+        &bytes.id,
+        &FractalUpdate {
+            name: Some("String".to_string()),
+        },
+        None,
+    );
+
+    // Assert
+    assert!(rust_string.is_ok(), "{:?}", rust_string);
+    assert!(in_context.is_ok(), "{:?}", in_context);
+    assert!(
+        matches!(sibling, Err(DataError::FractalAlreadyExists(_))),
+        "{:?}",
+        sibling
+    );
+    assert!(
+        matches!(relinked, Err(DataError::FractalAlreadyExists(_))),
+        "{:?}",
+        relinked
+    );
+    assert!(
+        matches!(renamed, Err(DataError::FractalAlreadyExists(_))),
+        "{:?}",
+        renamed
+    );
+    assert_ne!(rust_string.unwrap().id, python_string.id);
+}
+
+#[test]
+fn test_lookup_by_name_lists_every_candidate_with_its_paths() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let python = create_fractal(&conn, "Python", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let rust = create_fractal(&conn, "Rust", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    create_fractal(&conn, "String", Some(&python.id), None).unwrap();
+    create_fractal(&conn, "String", Some(&rust.id), None).unwrap();
+
+    // Act
+    let candidates = find_fractals_by_name(&conn, "String").unwrap();
+    let single = get_fractal_by_name(&conn, "String");
+
+    // Assert
+    let parents: Vec<Vec<&str>> = candidates
+        .iter()
+        .map(|c| {
+            c.paths
+                .iter()
+                .map(|p| p.fractals[p.fractals.len() - 2].name.as_str())
+                .collect()
+        })
+        .collect();
+    assert_eq!(parents, vec![vec!["Python"], vec!["Rust"]]);
+    assert!(
+        matches!(single, Err(DataError::AmbiguousName { count: 2, .. })),
+        "{:?}",
+        single
+    );
+}
+
+#[tokio::test]
+async fn test_same_named_fractals_are_told_apart_by_path() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = FRACTAL_ROOT_ID.to_string();
+    let music = create_named(&client, &address, "Music", &root_id).await;
+    let physics = create_named(&client, &address, "Physics", &root_id).await;
+    let music_string = create_named(&client, &address, "String", &music).await;
+    let physics_string = create_named(&client, &address, "String", &physics).await;
+
+    // Act
+    let candidates = graphql_request(
+        &client,
+        &address,
+        r#"
+            query {
+                fractalsByName(name: "String") {
+                    fractal { id }
+                    paths { fractals { name } }
+                }
+            }
+        "#,
+        json!({}),
+    )
+    .await;
+    let by_name = graphql_request(
+        &client,
+        &address,
+        r#"query { fractal(name: "String") { id } }"#,
+        json!({}),
+    )
+    .await;
+    let by_path = graphql_request(
+        &client,
+        &address,
+        r#"query { fractalByPath(path: "Physics/String") { id } }"#,
+        json!({}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(
+        candidates["data"]["fractalsByName"],
+        json!([
+            {
+                "fractal": {"id": music_string},
+                "paths": [{"fractals": [{"name": "Root"}, {"name": "Music"}, {"name": "String"}]}],
+            },
+            {
+                "fractal": {"id": physics_string},
+                "paths": [{"fractals": [{"name": "Root"}, {"name": "Physics"}, {"name": "String"}]}],
+            },
+        ])
+    );
+    assert_eq!(
+        by_name["errors"][0]["extensions"]["code"], "AMBIGUOUS_NAME",
+        "{:?}",
+        by_name
+    );
+    assert_eq!(by_path["data"]["fractalByPath"]["id"], physics_string);
+}
+
+#[test]
+fn test_root_cannot_be_renamed() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+
+    // Act
+    let renamed = update_fractal(
+        &conn,
+        &FRACTAL_ROOT_ID,
+        &FractalUpdate {
+            name: Some("Everything".to_string()),
+        },
+        None,
+    );
+    let unchanged = update_fractal(
+        &conn,
+        &FRACTAL_ROOT_ID,
+        &FractalUpdate {
+            name: Some("Root".to_string()),
+        },
+        None,
+    );
+
+    // Assert
+    assert!(
+        matches!(renamed, Err(DataError::RootFractalProtected)),
+        "{:?}",
+        renamed
+    );
+    assert!(unchanged.is_ok(), "{:?}", unchanged);
+    assert_eq!(
+        get_fractal_by_name(&conn, "Root").unwrap().id,
+        FRACTAL_ROOT_ID
+    );
+}
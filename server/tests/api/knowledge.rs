@@ -0,0 +1,333 @@
+use reqwest::Client;
+use serde_json::json;
+
+use crate::utils::{create_fractal, graphql_request, spawn_app};
+
+const ADD_KNOWLEDGE: &str = r#"
+    mutation ($input: AddKnowledgeInput!) {
+        addKnowledge(input: $input) {
+            id
+            content
+            fractal {
+                id
+                name
+            }
+            contexts {
+                name
+            }
+        }
+    }
+"#;
+
+#[tokio::test]
+async fn test_knowledge_resolves_owner_and_contexts() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    let mut ids = Vec::new();
+    for name in ["Programming", "Rust", "String"] {
+        let body = create_fractal(&client, &address, name, &root_id, vec![])
+            .await
+            .json::<serde_json::Value>()
+            .await
+            .unwrap();
+        ids.push(
+            body["data"]["createFractal"]["id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        ADD_KNOWLEDGE,
+        json!({"input": {
+            "fractalId": ids[2],
+            "content": "An owned, growable UTF-8 string",
+            "context": [ids[0], ids[1]],
+        }}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    let knowledge = &body["data"]["addKnowledge"];
+    assert_eq!(
+        knowledge["fractal"],
+        json!({"id": ids[2], "name": "String"})
+    );
+    let mut contexts = knowledge["contexts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    contexts.sort();
+    assert_eq!(contexts, vec!["Programming", "Rust"]);
+}
+
+#[tokio::test]
+async fn test_fractal_knowledges_are_paginated_in_creation_order() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    let body = create_fractal(&client, &address, "Rust", &root_id, vec![])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    let rust_id = body["data"]["createFractal"]["id"].as_str().unwrap();
+
+    let mut knowledge_ids = Vec::new();
+    for (content, context) in [
+        ("first", vec![rust_id]),
+        ("second", vec![]),
+        ("third", vec![rust_id]),
+    ] {
+        let added = graphql_request(
+            &client,
+            &address,
+            ADD_KNOWLEDGE,
+            json!({"input": {"fractalId": root_id, "content": content, "context": context}}),
+        )
+        .await;
+        knowledge_ids.push(added["data"]["addKnowledge"]["id"].clone());
+    }
+
+    let query = r#"
+        query ($first: Int, $after: String, $context: [UUID!]) {
+            fractal {
+                knowledges(first: $first, after: $after, context: $context) {
+                    edges {
+                        cursor
+                        node {
+                            content
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        }
+    "#;
+    let contents = |body: &serde_json::Value| {
+        body["data"]["fractal"]["knowledges"]["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| edge["node"]["content"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Act
+    let in_context = graphql_request(&client, &address, query, json!({"context": [rust_id]})).await;
+    let first_page = graphql_request(&client, &address, query, json!({"first": 2})).await;
+    let end_cursor = first_page["data"]["fractal"]["knowledges"]["pageInfo"]["endCursor"].clone();
+    // Entries deleted between pages must not shift where the next page starts
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($id: UUID!) { deleteKnowledge(id: $id) }",
+        json!({"id": knowledge_ids[0]}),
+    )
+    .await;
+    let second_page = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"first": 2, "after": end_cursor}),
+    )
+    .await;
+
+    // Assert
+    assert!(first_page.get("errors").is_none(), "{:?}", first_page);
+    assert_eq!(contents(&first_page), vec!["first", "second"]);
+    assert_eq!(
+        first_page["data"]["fractal"]["knowledges"]["pageInfo"]["hasNextPage"],
+        true
+    );
+    assert_eq!(contents(&second_page), vec!["third"]);
+    assert_eq!(
+        second_page["data"]["fractal"]["knowledges"]["pageInfo"]["hasNextPage"],
+        false
+    );
+    assert_eq!(contents(&in_context), vec!["first", "third"]);
+}
+
+#[tokio::test]
+async fn test_knowledge_query_returns_every_match() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    for content in ["one", "two"] {
+        graphql_request(
+            &client,
+            &address,
+            ADD_KNOWLEDGE,
+            json!({"input": {"fractalId": root_id, "content": content, "context": []}}),
+        )
+        .await;
+    }
+
+    let query = r#"
+        query {
+            knowledge(fractalName: "Root", context: []) {
+                content
+            }
+        }
+    "#;
+
+    // Act
+    let body = graphql_request(&client, &address, query, json!({})).await;
+
+    // Assert
+    assert_eq!(
+        body["data"]["knowledge"],
+        json!([{"content": "one"}, {"content": "two"}])
+    );
+}
+
+#[tokio::test]
+async fn test_knowledge_can_be_edited_retagged_and_moved() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    let mut ids = Vec::new();
+    for name in ["Rust", "String"] {
+        let body = create_fractal(&client, &address, name, &root_id, vec![])
+            .await
+            .json::<serde_json::Value>()
+            .await
+            .unwrap();
+        ids.push(
+            body["data"]["createFractal"]["id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+    let body = graphql_request(
+        &client,
+        &address,
+        ADD_KNOWLEDGE,
+        json!({"input": {"fractalId": root_id, "content": "draft", "context": []}}),
+    )
+    .await;
+    let knowledge_id = body["data"]["addKnowledge"]["id"].clone();
+    let fields = "id content updatedAt fractal { name } contexts { name }";
+
+    // Act
+    let updated = graphql_request(
+        &client,
+        &address,
+        &format!(
+            "mutation ($input: UpdateKnowledgeInput!) {{ updateKnowledge(input: $input) {{ {} }} }}",
+            fields
+        ),
+        json!({"input": {"id": knowledge_id, "content": "An owned string"}}),
+    )
+    .await;
+    let retagged = graphql_request(
+        &client,
+        &address,
+        &format!(
+            "mutation ($id: UUID!, $contextIds: [UUID!]!) {{ setKnowledgeContexts(id: $id, contextIds: $contextIds) {{ {} }} }}",
+            fields
+        ),
+        json!({"id": knowledge_id, "contextIds": [ids[0]]}),
+    )
+    .await;
+    let moved = graphql_request(
+        &client,
+        &address,
+        &format!(
+            "mutation ($id: UUID!, $fractalId: UUID!) {{ moveKnowledge(id: $id, fractalId: $fractalId) {{ {} }} }}",
+            fields
+        ),
+        json!({"id": knowledge_id, "fractalId": ids[1]}),
+    )
+    .await;
+
+    // Assert
+    assert!(updated.get("errors").is_none(), "{:?}", updated);
+    assert_eq!(
+        updated["data"]["updateKnowledge"]["content"],
+        "An owned string"
+    );
+    assert_ne!(
+        updated["data"]["updateKnowledge"]["updatedAt"],
+        body["data"]["addKnowledge"]["updatedAt"]
+    );
+
+    assert!(retagged.get("errors").is_none(), "{:?}", retagged);
+    assert_eq!(
+        retagged["data"]["setKnowledgeContexts"]["contexts"],
+        json!([{"name": "Rust"}])
+    );
+
+    assert!(moved.get("errors").is_none(), "{:?}", moved);
+    let moved = &moved["data"]["moveKnowledge"];
+    assert_eq!(moved["fractal"], json!({"name": "String"}));
+    assert_eq!(moved["contexts"], json!([{"name": "Rust"}]));
+    assert_eq!(moved["content"], "An owned string");
+}
+
+#[tokio::test]
+async fn test_unknown_knowledge_ids_are_not_found() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = server::data::FRACTAL_ROOT_ID.to_string();
+
+    let body = graphql_request(
+        &client,
+        &address,
+        ADD_KNOWLEDGE,
+        json!({"input": {"fractalId": root_id, "content": "short-lived", "context": []}}),
+    )
+    .await;
+    let knowledge_id = body["data"]["addKnowledge"]["id"].clone();
+    let delete = "mutation ($id: UUID!) { deleteKnowledge(id: $id) }";
+
+    // Act
+    let deleted = graphql_request(&client, &address, delete, json!({"id": knowledge_id})).await;
+    let deleted_again =
+        graphql_request(&client, &address, delete, json!({"id": knowledge_id})).await;
+    let updated = graphql_request(
+        &client,
+        &address,
+        "mutation ($input: UpdateKnowledgeInput!) { updateKnowledge(input: $input) { id } }",
+        json!({"input": {"id": knowledge_id, "content": "too late"}}),
+    )
+    .await;
+    let moved_to_unknown = graphql_request(
+        &client,
+        &address,
+        "mutation ($id: UUID!, $fractalId: UUID!) { moveKnowledge(id: $id, fractalId: $fractalId) { id } }",
+        json!({"id": knowledge_id, "fractalId": uuid::Uuid::new_v4()}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(deleted["data"]["deleteKnowledge"], true);
+    for body in [deleted_again, updated, moved_to_unknown] {
+        assert_eq!(
+            body["errors"][0]["extensions"]["code"], "NOT_FOUND",
+            "{:?}",
+            body
+        );
+    }
+}
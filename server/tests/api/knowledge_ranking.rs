@@ -0,0 +1,155 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{create_connection, rank_fractal_knowledge, FRACTAL_ROOT_ID};
+
+use crate::utils::{create_fractal, example_db, graphql_request, id_of, spawn_app};
+
+#[test]
+fn test_knowledge_matching_more_of_the_context_stack_ranks_first() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let string = id_of(&conn, "String");
+    let programming = id_of(&conn, "Programming");
+    let rust = id_of(&conn, "Rust");
+    let ownership = id_of(&conn, "Ownership");
+
+    // Act
+    let ranked =
+        rank_fractal_knowledge(&conn, &string, &[programming, rust, ownership], None).unwrap();
+
+    // Assert
+    let contents = ranked
+        .iter()
+        .map(|r| r.knowledge.content.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        contents,
+        vec![
+            "Moving a String transfers ownership of its heap buffer instead of copying it.",
+            "Rust's String is an owned, growable UTF-8 buffer.",
+            "A string is a sequence of characters.",
+        ]
+    );
+    assert!((ranked[0].score - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(ranked[0].matched_context_ids, vec![rust, ownership]);
+    assert!((ranked[1].score - 1.0 / 3.0).abs() < 1e-9);
+    assert!((ranked[2].score - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_ancestor_contexts_count_partially() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let string = id_of(&conn, "String");
+    let ownership = id_of(&conn, "Ownership");
+
+    // Act
+    let ranked = rank_fractal_knowledge(&conn, &string, &[ownership], None).unwrap();
+
+    // Assert
+    let scores = ranked
+        .iter()
+        .map(|r| (r.knowledge.content.as_str(), r.score))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        scores,
+        vec![
+            (
+                "Moving a String transfers ownership of its heap buffer instead of copying it.",
+                1.0
+            ),
+            ("Rust's String is an owned, growable UTF-8 buffer.", 0.5),
+            ("A string is a sequence of characters.", 0.25),
+        ]
+    );
+    assert!(ranked
+        .iter()
+        .all(|r| r.matched_context_ids == vec![ownership]));
+}
+
+#[test]
+fn test_ranking_without_context_returns_all_knowledge_oldest_first() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let string = id_of(&conn, "String");
+
+    // Act
+    let ranked = rank_fractal_knowledge(&conn, &string, &[], Some(2)).unwrap();
+
+    // Assert
+    assert_eq!(ranked.len(), 2);
+    assert_eq!(
+        ranked[0].knowledge.content,
+        "A string is a sequence of characters."
+    );
+    assert!(ranked.iter().all(|r| r.score == 1.0));
+}
+
+#[tokio::test]
+async fn test_best_knowledge_query_returns_scores() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = FRACTAL_ROOT_ID.to_string();
+
+    let mut ids = Vec::new();
+    for name in ["Programming", "Rust", "String"] {
+        let body = create_fractal(&client, &address, name, &root_id, vec![])
+            .await
+            .json::<serde_json::Value>()
+            .await
+            .unwrap();
+        ids.push(
+            body["data"]["createFractal"]["id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($parent: UUID!, $child: UUID!) { addRelation(parentId: $parent, childId: $child) }",
+        json!({"parent": ids[0], "child": ids[1]}),
+    )
+    .await;
+    for (content, context) in [("General", &ids[0]), ("Specific", &ids[1])] {
+        graphql_request(
+            &client,
+            &address,
+            "mutation ($input: AddKnowledgeInput!) { addKnowledge(input: $input) { id } }",
+            json!({"input": {"fractalId": ids[2], "content": content, "context": [context]}}),
+        )
+        .await;
+    }
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        r#"
+            query ($context: [UUID!]!) {
+                bestKnowledge(fractalName: "String", context: $context) {
+                    knowledge { content }
+                    score
+                    matchedContextIds
+                }
+            }
+        "#,
+        json!({"context": [ids[1]]}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["bestKnowledge"],
+        json!([
+            {"knowledge": {"content": "Specific"}, "score": 1.0, "matchedContextIds": [ids[1]]},
+            {"knowledge": {"content": "General"}, "score": 0.5, "matchedContextIds": [ids[1]]},
+        ])
+    );
+}
@@ -1,4 +1,18 @@
+mod config;
+mod database;
 mod fractal;
+mod fractal_aliases;
 mod fractal_context;
+mod fractal_deletion;
+mod fractal_hierarchy;
+mod fractal_move;
+mod fractal_names;
 mod health_check;
+mod knowledge;
+mod knowledge_ranking;
+mod migrations;
+mod paths;
+mod subgraph;
+mod transactions;
+mod trash;
 mod utils;
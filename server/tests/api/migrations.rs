@@ -0,0 +1,138 @@
+use server::data::{
+    create_connection, create_db, create_fractal, get_fractal_by_name, get_schema_version,
+    init_database, latest_schema_version, migrate, resolve_path, DataError, Migration,
+    FRACTAL_ROOT_ID, IN_MEMORY_DB_PATH, MIGRATIONS,
+};
+
+/// A migration one version past the newest real one, standing in for the next schema change.
+fn next_migration() -> Migration {
+    Migration {
+        version: latest_schema_version() + 1,
+        description: "Add Fractal.description",
+        statements: &["ALTER TABLE Fractal ADD description STRING DEFAULT ''"],
+    }
+}
+
+fn with_next_migration() -> Vec<Migration> {
+    let mut migrations = MIGRATIONS.to_vec();
+    migrations.push(next_migration());
+    migrations
+}
+
+#[test]
+fn test_init_database_migrates_to_latest_version() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    assert_eq!(get_schema_version(&conn).unwrap(), 0);
+
+    // Act
+    init_database(&conn).expect("Failed to initialize database.");
+    // Running it again must be a no-op
+    init_database(&conn).expect("Failed to re-initialize database.");
+
+    // Assert
+    assert_eq!(get_schema_version(&conn).unwrap(), latest_schema_version());
+}
+
+#[test]
+fn test_populated_v1_database_migrates_forward() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    assert_eq!(migrate(&conn, &MIGRATIONS[..1]).unwrap(), 1);
+    // Written with only the v1 properties, the way a v1 build stored them
+    conn.query(&format!(
+        "CREATE (:Fractal {{id: UUID('{}'), name: 'Root', createdAt: timestamp('2024-01-01 00:00:00'), updatedAt: timestamp('2024-01-01 00:00:00')}})",
+        FRACTAL_ROOT_ID
+    ))
+    .expect("Failed to write v1 data");
+    let seeded = conn
+        .query("MATCH (f:Fractal) RETURN f.name")
+        .expect("Failed to read v1 data");
+    assert_eq!(seeded.count(), 1);
+
+    // Act
+    let version = migrate(&conn, &with_next_migration()).expect("Failed to migrate forward");
+
+    // Assert
+    let expected = latest_schema_version() + 1;
+    assert_eq!(version, expected);
+    assert_eq!(get_schema_version(&conn).unwrap(), expected);
+    assert_eq!(
+        get_fractal_by_name(&conn, "Root").unwrap().id,
+        FRACTAL_ROOT_ID
+    );
+    let result = conn
+        .query("MATCH (f:Fractal) RETURN f.description")
+        .expect("Migration did not add the new property");
+    assert_eq!(result.count(), 1);
+}
+
+#[test]
+fn test_globally_unique_names_stay_valid_when_uniqueness_is_scoped() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    migrate(&conn, &MIGRATIONS[..2]).expect("Failed to migrate to v2");
+    // Written the way a build with one global namespace for names stored them
+    conn.query(&format!(
+        "CREATE (root:Fractal {{id: UUID('{}'), name: 'Root', createdAt: timestamp('2024-01-01 00:00:00'), updatedAt: timestamp('2024-01-01 00:00:00')}})
+         CREATE (programming:Fractal {{id: gen_random_uuid(), name: 'Programming', createdAt: timestamp('2024-01-01 00:00:00'), updatedAt: timestamp('2024-01-01 00:00:00')}})
+         CREATE (rust:Fractal {{id: gen_random_uuid(), name: 'Rust', createdAt: timestamp('2024-01-01 00:00:00'), updatedAt: timestamp('2024-01-01 00:00:00')}})
+         CREATE (root)-[:HAS_CHILD]->(programming)
+         CREATE (programming)-[:HAS_CHILD]->(rust)",
+        FRACTAL_ROOT_ID
+    ))
+    .expect("Failed to write v2 data");
+
+    // Act
+    init_database(&conn).expect("Failed to migrate to the latest version");
+    let by_name = get_fractal_by_name(&conn, "Rust").unwrap();
+    let by_path = resolve_path(&conn, "Programming/Rust").unwrap();
+    let elsewhere = create_fractal(&conn, "Rust", Some(&FRACTAL_ROOT_ID), None);
+    let ambiguous = get_fractal_by_name(&conn, "Rust");
+
+    // Assert
+    assert_eq!(by_path.fractals[2].id, by_name.id);
+    assert!(elsewhere.is_ok(), "{:?}", elsewhere);
+    assert!(
+        matches!(ambiguous, Err(DataError::AmbiguousName { count: 2, .. })),
+        "{:?}",
+        ambiguous
+    );
+}
+
+#[test]
+fn test_newer_schema_version_is_refused() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    migrate(&conn, &with_next_migration()).expect("Failed to migrate forward");
+
+    // Act
+    let result = migrate(&conn, MIGRATIONS);
+
+    // Assert
+    let latest = latest_schema_version();
+    assert!(
+        matches!(
+            result,
+            Err(DataError::SchemaVersionTooNew { found, supported })
+                if found == latest + 1 && supported == latest
+        ),
+        "{:?}",
+        result
+    );
+}
+
+#[test]
+fn test_out_of_order_migrations_are_rejected() {
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+
+    let result = migrate(&conn, &[next_migration()]);
+
+    assert!(matches!(result, Err(DataError::InvalidData(_))));
+    assert_eq!(get_schema_version(&conn).unwrap(), 0);
+}
@@ -0,0 +1,429 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    add_has_child_edge, create_connection, create_fractal, find_paths, get_paths_from_root,
+    resolve_path, DataError, FractalPath, RelationType, FRACTAL_ROOT_ID, MAX_PATH_LENGTH,
+};
+
+use crate::utils::{empty_db, example_db, graphql_request, id_of, spawn_app};
+
+fn names(path: &FractalPath) -> Vec<&str> {
+    path.fractals.iter().map(|f| f.name.as_str()).collect()
+}
+
+#[test]
+fn test_paths_connect_siblings_through_their_parent() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let count = id_of(&conn, ".count()");
+    let amp_str = id_of(&conn, "&str");
+    let string = id_of(&conn, "String");
+
+    // Act
+    let paths = find_paths(
+        &conn,
+        &count,
+        &amp_str,
+        MAX_PATH_LENGTH,
+        &[RelationType::HasChild, RelationType::HasContext],
+        3,
+    )
+    .unwrap();
+
+    // Assert
+    assert_eq!(paths.len(), 1);
+    assert_eq!(names(&paths[0]), vec![".count()", "String", "&str"]);
+    let edges = &paths[0].edges;
+    assert!(edges
+        .iter()
+        .all(|e| e.relation_type == RelationType::HasChild && e.source_id == string));
+    assert_eq!(edges[0].context_id, Some(id_of(&conn, "Python")));
+    assert_eq!(edges[1].context_id, Some(id_of(&conn, "Rust")));
+}
+
+#[test]
+fn test_paths_are_shortest_first_and_bounded() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let ownership = id_of(&conn, "Ownership");
+    let string = id_of(&conn, "String");
+    let lengths =
+        |paths: Vec<FractalPath>| -> Vec<usize> { paths.iter().map(|p| p.edges.len()).collect() };
+
+    // Act
+    let shortest = find_paths(
+        &conn,
+        &ownership,
+        &string,
+        MAX_PATH_LENGTH,
+        &[RelationType::HasChild],
+        3,
+    )
+    .unwrap();
+    let bounded = find_paths(&conn, &ownership, &string, 2, &[RelationType::HasChild], 3).unwrap();
+
+    // Assert
+    assert_eq!(names(&shortest[0]), vec!["Ownership", "Rust", "String"]);
+    assert_eq!(lengths(shortest), vec![2, 3, 4]);
+    assert_eq!(lengths(bounded), vec![2]);
+}
+
+#[test]
+fn test_paths_of_the_same_length_come_in_a_stable_order() {
+    // Arrange
+    let db = empty_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let mut middles = Vec::new();
+    for name in ["Left", "Middle", "Right"] {
+        middles.push(
+            create_fractal(&conn, name, Some(&FRACTAL_ROOT_ID), None)
+                .unwrap()
+                .id,
+        );
+    }
+    let bottom = create_fractal(&conn, "Bottom", Some(&middles[0]), None).unwrap();
+    for middle in &middles[1..] {
+        add_has_child_edge(&conn, middle, &bottom.id, None).unwrap();
+    }
+    middles.sort_by_key(|id| id.to_string());
+    let find = |k| {
+        find_paths(
+            &conn,
+            &FRACTAL_ROOT_ID,
+            &bottom.id,
+            MAX_PATH_LENGTH,
+            &[RelationType::HasChild],
+            k,
+        )
+        .unwrap()
+    };
+
+    // Act
+    let first = find(1);
+    let all = find(3);
+
+    // Assert
+    assert_eq!(first[0].fractals[1].id, middles[0]);
+    assert_eq!(
+        all.iter().map(|p| p.fractals[1].id).collect::<Vec<_>>(),
+        middles
+    );
+}
+
+#[tokio::test]
+async fn test_paths_follow_context_links_and_reject_knowledge() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let create = "mutation ($input: CreateFractalInput!) { createFractal(input: $input) { id } }";
+    let language = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Language", "parentId": FRACTAL_ROOT_ID}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let grammar = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Grammar", "parentId": FRACTAL_ROOT_ID, "contexts": [language]}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let query = r#"
+        query ($fromId: UUID!, $toId: UUID!, $relationTypes: [RelationType!]) {
+            paths(fromId: $fromId, toId: $toId, relationTypes: $relationTypes, k: 2) {
+                length
+                fractals { name }
+                edges { relationType sourceId targetId contextId }
+            }
+        }
+    "#;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"fromId": language, "toId": grammar}),
+    )
+    .await;
+    let knowledge = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"fromId": language, "toId": grammar, "relationTypes": ["HAS_KNOWLEDGE"]}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["paths"],
+        json!([
+            {
+                "length": 1,
+                "fractals": [{"name": "Language"}, {"name": "Grammar"}],
+                "edges": [
+                    {"relationType": "HAS_CONTEXT", "sourceId": grammar, "targetId": language, "contextId": null},
+                ],
+            },
+            {
+                "length": 2,
+                "fractals": [{"name": "Language"}, {"name": "Root"}, {"name": "Grammar"}],
+                "edges": [
+                    {"relationType": "HAS_CHILD", "sourceId": FRACTAL_ROOT_ID, "targetId": language, "contextId": null},
+                    {"relationType": "HAS_CHILD", "sourceId": FRACTAL_ROOT_ID, "targetId": grammar, "contextId": null},
+                ],
+            },
+        ])
+    );
+    assert_eq!(
+        knowledge["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        knowledge
+    );
+}
+
+#[test]
+fn test_paths_from_root_list_every_parent_chain() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let string = id_of(&conn, "String");
+    let rust = id_of(&conn, "Rust");
+
+    // Act
+    let all = get_paths_from_root(&conn, &string, None, None).unwrap();
+    let through_rust = get_paths_from_root(&conn, &string, None, Some(&rust)).unwrap();
+    let count = get_paths_from_root(&conn, &id_of(&conn, ".count()"), None, None).unwrap();
+
+    // Assert
+    assert_eq!(
+        all.iter().map(names).collect::<Vec<_>>(),
+        vec![
+            vec!["Root", "Programming", "String"],
+            vec!["Root", "Programming", "Python", "String"],
+            vec!["Root", "Programming", "Rust", "String"],
+        ]
+    );
+    assert_eq!(
+        all[0]
+            .edges
+            .iter()
+            .map(|e| e.context_id)
+            .collect::<Vec<_>>(),
+        vec![None, Some(FRACTAL_ROOT_ID)]
+    );
+    assert_eq!(through_rust.len(), 1);
+    assert_eq!(
+        names(&through_rust[0]),
+        vec!["Root", "Programming", "Rust", "String"]
+    );
+    assert_eq!(
+        through_rust[0].edges[2].context_id,
+        Some(id_of(&conn, "Programming"))
+    );
+    // String only links to .count() in Python, so that is the only way to get there
+    assert_eq!(
+        count.iter().map(names).collect::<Vec<_>>(),
+        vec![vec!["Root", "Programming", "Python", "String", ".count()"]]
+    );
+}
+
+#[tokio::test]
+async fn test_paths_from_root_carry_the_context_of_each_link() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let create = "mutation ($input: CreateFractalInput!) { createFractal(input: $input) { id } }";
+    let music = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Music", "parentId": FRACTAL_ROOT_ID}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {
+            "name": "Chord",
+            "parentId": FRACTAL_ROOT_ID,
+            "parents": [{"parentId": music, "contextId": music}],
+        }}),
+    )
+    .await;
+    // Root only links to Scale in the context of Music, which never comes before it on a path
+    graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Scale", "parentId": FRACTAL_ROOT_ID, "contextIds": [music]}}),
+    )
+    .await;
+    let query = r#"
+        query ($name: String, $contextId: UUID, $throughId: UUID) {
+            fractal(name: $name) {
+                pathsFromRoot(contextId: $contextId, throughId: $throughId) {
+                    fractals { name }
+                    edges { contextId }
+                }
+            }
+        }
+    "#;
+
+    // Act
+    let body = graphql_request(&client, &address, query, json!({"name": "Chord"})).await;
+    let through_music = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"name": "Chord", "throughId": music}),
+    )
+    .await;
+    let scale = graphql_request(&client, &address, query, json!({"name": "Scale"})).await;
+    let scale_in_music = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"name": "Scale", "contextId": music}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["fractal"]["pathsFromRoot"],
+        json!([
+            {
+                "fractals": [{"name": "Root"}, {"name": "Chord"}],
+                "edges": [{"contextId": null}],
+            },
+            {
+                "fractals": [{"name": "Root"}, {"name": "Music"}, {"name": "Chord"}],
+                "edges": [{"contextId": null}, {"contextId": music}],
+            },
+        ])
+    );
+    assert_eq!(
+        through_music["data"]["fractal"]["pathsFromRoot"],
+        json!([{
+            "fractals": [{"name": "Root"}, {"name": "Music"}, {"name": "Chord"}],
+            "edges": [{"contextId": null}, {"contextId": music}],
+        }])
+    );
+    assert_eq!(
+        scale["data"]["fractal"]["pathsFromRoot"],
+        json!([]),
+        "{:?}",
+        scale
+    );
+    assert_eq!(
+        scale_in_music["data"]["fractal"]["pathsFromRoot"],
+        json!([{
+            "fractals": [{"name": "Root"}, {"name": "Scale"}],
+            "edges": [{"contextId": music}],
+        }])
+    );
+}
+
+#[test]
+fn test_resolve_path_respects_the_context_of_each_link() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+
+    // Act
+    let count = resolve_path(&conn, "Programming/Python/String/.count()").unwrap();
+    let rust = resolve_path(&conn, "/Programming/Rust/").unwrap();
+    let root = resolve_path(&conn, "").unwrap();
+    let out_of_context = resolve_path(&conn, "Programming/String/.count()").unwrap_err();
+    let missing = resolve_path(&conn, "Programming/Go/String").unwrap_err();
+
+    // Assert
+    assert_eq!(
+        names(&count),
+        vec!["Root", "Programming", "Python", "String", ".count()"]
+    );
+    assert_eq!(count.edges[3].context_id, Some(id_of(&conn, "Python")));
+    assert_eq!(names(&rust), vec!["Root", "Programming", "Rust"]);
+    assert_eq!(names(&root), vec!["Root"]);
+    assert!(
+        matches!(
+            &out_of_context,
+            DataError::PathSegmentNotFound { segment, parent, .. }
+                if segment == ".count()" && parent == "String"
+        ),
+        "{:?}",
+        out_of_context
+    );
+    assert!(
+        matches!(&missing, DataError::PathSegmentNotFound { segment, .. } if segment == "Go"),
+        "{:?}",
+        missing
+    );
+}
+
+#[tokio::test]
+async fn test_fractal_by_path_names_the_segment_that_failed() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let create = "mutation ($input: CreateFractalInput!) { createFractal(input: $input) { id } }";
+    let music = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Music", "parentId": FRACTAL_ROOT_ID}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let chord = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Chord", "parentId": music}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let query = "query ($path: String!) { fractalByPath(path: $path) { id } }";
+
+    // Act
+    let found = graphql_request(&client, &address, query, json!({"path": "Music/Chord"})).await;
+    let missing = graphql_request(
+        &client,
+        &address,
+        query,
+        json!({"path": "Music/Scale/Chord"}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(found["data"]["fractalByPath"]["id"], chord, "{:?}", found);
+    assert_eq!(
+        missing["errors"][0]["extensions"]["code"], "NOT_FOUND",
+        "{:?}",
+        missing
+    );
+    assert!(missing["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("'Scale'"));
+}
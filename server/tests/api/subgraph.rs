@@ -0,0 +1,208 @@
+use reqwest::Client;
+use serde_json::json;
+use server::data::{
+    create_connection, get_subgraph, RelationType, FRACTAL_ROOT_ID, MAX_SUBGRAPH_NODES,
+};
+use std::collections::HashSet;
+
+use crate::utils::{example_db, graphql_request, id_of, spawn_app};
+
+#[test]
+fn test_subgraph_collects_fractals_knowledge_and_edges() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let rust = id_of(&conn, "Rust");
+
+    // Act
+    let subgraph = get_subgraph(
+        &conn,
+        &rust,
+        2,
+        &RelationType::ALL,
+        None,
+        MAX_SUBGRAPH_NODES,
+    )
+    .unwrap();
+
+    // Assert
+    assert_eq!(subgraph.fractals[0].id, rust);
+    let names: HashSet<&str> = subgraph.fractals.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        names,
+        HashSet::from([
+            "Rust",
+            "Ownership",
+            "String",
+            ".count()",
+            "String literal",
+            "&str"
+        ])
+    );
+    assert_eq!(subgraph.fractals.len(), names.len());
+    assert_eq!(subgraph.knowledge.len(), 4);
+    assert!(!subgraph.truncated);
+
+    let count = |relation_type| {
+        subgraph
+            .edges
+            .iter()
+            .filter(|e| e.relation_type == relation_type)
+            .count()
+    };
+    assert_eq!(count(RelationType::HasChild), 5);
+    assert_eq!(count(RelationType::HasKnowledge), 4);
+    assert_eq!(count(RelationType::HasContext), 0);
+}
+
+#[test]
+fn test_subgraph_follows_only_the_requested_context_and_types() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let rust = id_of(&conn, "Rust");
+    let programming = id_of(&conn, "Programming");
+
+    // Act
+    let subgraph = get_subgraph(
+        &conn,
+        &rust,
+        2,
+        &[RelationType::HasChild],
+        Some(&programming),
+        MAX_SUBGRAPH_NODES,
+    )
+    .unwrap();
+
+    // Assert
+    let names: HashSet<&str> = subgraph.fractals.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        names,
+        HashSet::from(["Rust", "Ownership", "String", "String literal"])
+    );
+    assert!(subgraph.knowledge.is_empty());
+    assert_eq!(subgraph.edges.len(), 3);
+    assert!(subgraph
+        .edges
+        .iter()
+        .all(|e| e.context_id == Some(programming)));
+}
+
+#[test]
+fn test_subgraph_stops_at_the_node_limit() {
+    // Arrange
+    let db = example_db();
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let rust = id_of(&conn, "Rust");
+
+    // Act
+    let subgraph = get_subgraph(&conn, &rust, 2, &RelationType::ALL, None, 3).unwrap();
+
+    // Assert
+    assert!(subgraph.truncated);
+    assert_eq!(subgraph.fractals[0].id, rust);
+    let ids: HashSet<_> = subgraph
+        .fractals
+        .iter()
+        .map(|f| f.id)
+        .chain(subgraph.knowledge.iter().map(|k| k.id))
+        .collect();
+    assert_eq!(ids.len(), 3);
+    assert!(subgraph
+        .edges
+        .iter()
+        .all(|e| ids.contains(&e.source_id) && ids.contains(&e.target_id)));
+}
+
+#[tokio::test]
+async fn test_subgraph_lists_each_node_and_edge_once() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let create = "mutation ($input: CreateFractalInput!) { createFractal(input: $input) { id } }";
+    let language = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Language", "parentId": FRACTAL_ROOT_ID}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let grammar = graphql_request(
+        &client,
+        &address,
+        create,
+        json!({"input": {"name": "Grammar", "parentId": language, "contexts": [language]}}),
+    )
+    .await["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        r#"
+            query ($rootId: UUID!) {
+                subgraph(rootId: $rootId, depth: 5) {
+                    nodes {
+                        id
+                    }
+                    edges {
+                        relationType
+                        sourceId
+                        targetId
+                        contextId
+                    }
+                    truncated
+                }
+            }
+        "#,
+        json!({"rootId": language}),
+    )
+    .await;
+    let limited = "query ($rootId: UUID!, $maxNodes: Int) {
+        subgraph(rootId: $rootId, depth: 5, maxNodes: $maxNodes) { nodes { id } edges { sourceId } truncated }
+    }";
+    let root_only = graphql_request(
+        &client,
+        &address,
+        limited,
+        json!({"rootId": language, "maxNodes": 1}),
+    )
+    .await;
+    let empty = graphql_request(
+        &client,
+        &address,
+        limited,
+        json!({"rootId": language, "maxNodes": 0}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(
+        body["data"]["subgraph"]["nodes"],
+        json!([{"id": language}, {"id": grammar}])
+    );
+    assert_eq!(
+        body["data"]["subgraph"]["edges"],
+        json!([
+            {"relationType": "HAS_CHILD", "sourceId": language, "targetId": grammar, "contextId": null},
+            {"relationType": "HAS_CONTEXT", "sourceId": grammar, "targetId": language, "contextId": null},
+        ])
+    );
+    assert_eq!(body["data"]["subgraph"]["truncated"], false);
+    assert_eq!(
+        root_only["data"]["subgraph"],
+        json!({"nodes": [{"id": language}], "edges": [], "truncated": true})
+    );
+    assert_eq!(
+        empty["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        empty
+    );
+}
@@ -0,0 +1,96 @@
+use server::data::{
+    create_connection, create_db, create_fractal, create_fractal_raw, get_fractal_by_name,
+    get_fractal_relations, init_database, with_transaction, DataError, FRACTAL_ROOT_ID,
+    IN_MEMORY_DB_PATH,
+};
+
+#[test]
+fn test_concurrent_creates_yield_exactly_one_winner() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    {
+        let conn = create_connection(&db).expect("Failed to create connection.");
+        init_database(&conn).expect("Failed to initialize database.");
+        create_fractal_raw(&conn, "Root", None, None, Some(FRACTAL_ROOT_ID))
+            .expect("Failed to create Root fractal.");
+    }
+
+    // Act
+    let results = std::thread::scope(|scope| {
+        let handles = (0..8)
+            .map(|_| {
+                scope.spawn(|| {
+                    let conn = create_connection(&db).expect("Failed to create connection.");
+                    create_fractal(&conn, "Contested", Some(&FRACTAL_ROOT_ID), None)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    // Assert
+    let winners = results.iter().filter(|result| result.is_ok()).count();
+    assert_eq!(winners, 1, "{:?}", results);
+    for result in results.iter().filter(|result| result.is_err()) {
+        assert!(
+            matches!(result, Err(DataError::FractalAlreadyExists(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    let children = get_fractal_relations(&conn, &FRACTAL_ROOT_ID, "children").unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].name, "Contested");
+}
+
+#[test]
+fn test_failed_transaction_is_rolled_back() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    init_database(&conn).expect("Failed to initialize database.");
+
+    // Act
+    let result: Result<(), DataError> = with_transaction(&conn, |conn| {
+        conn.query(
+            "CREATE (:Fractal {id: UUID('00000000-0000-0000-0000-000000000001'), name: 'Orphan'})",
+        )?;
+        Err(DataError::InvalidData("edge insert failed".to_string()))
+    });
+
+    // Assert
+    assert!(matches!(result, Err(DataError::InvalidData(_))));
+    assert!(matches!(
+        get_fractal_by_name(&conn, "Orphan"),
+        Err(DataError::FractalNotFound(_))
+    ));
+}
+
+#[test]
+fn test_create_fractal_under_unknown_parent_creates_nothing() {
+    // Arrange
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    let conn = create_connection(&db).expect("Failed to create connection.");
+    init_database(&conn).expect("Failed to initialize database.");
+    let missing = uuid::Uuid::new_v4();
+
+    // Act
+    let result = create_fractal(&conn, "Orphan", Some(&missing), None);
+
+    // Assert
+    assert!(
+        matches!(&result, Err(DataError::FractalNotFound(id)) if *id == missing.to_string()),
+        "{:?}",
+        result
+    );
+    assert!(matches!(
+        get_fractal_by_name(&conn, "Orphan"),
+        Err(DataError::FractalNotFound(_))
+    ));
+}
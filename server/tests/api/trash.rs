@@ -0,0 +1,342 @@
+use chrono::{Duration, Utc};
+use reqwest::Client;
+use serde_json::json;
+use server::data::{self, DeleteMode, FRACTAL_ROOT_ID};
+use uuid::Uuid;
+
+use crate::utils::{create_fractal, create_named, empty_db, graphql_request, spawn_app};
+
+const DELETE_FRACTAL: &str = r#"
+    mutation ($id: UUID!, $mode: DeleteMode, $deletedBy: String) {
+        deleteFractal(id: $id, mode: $mode, deletedBy: $deletedBy) {
+            deletedFractals {
+                name
+            }
+        }
+    }
+"#;
+
+const TRASH: &str = r#"
+    query {
+        trash {
+            node {
+                id
+                ... on FractalGraphQL { name }
+                ... on KnowledgeGraphQL { content }
+            }
+            deletedAt
+            deletedBy
+        }
+    }
+"#;
+
+async fn add_knowledge(client: &Client, address: &str, fractal_id: &str, content: &str) -> String {
+    let body = graphql_request(
+        client,
+        address,
+        "mutation ($input: AddKnowledgeInput!) { addKnowledge(input: $input) { id } }",
+        json!({"input": {"fractalId": fractal_id, "content": content, "context": []}}),
+    )
+    .await;
+    body["data"]["addKnowledge"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn child_names(client: &Client, address: &str, name: &str) -> serde_json::Value {
+    let body = graphql_request(
+        client,
+        address,
+        "query ($name: String) { fractal(name: $name) { children { name } } }",
+        json!({"name": name}),
+    )
+    .await;
+    body["data"]["fractal"]["children"].clone()
+}
+
+#[tokio::test]
+async fn test_deleted_fractal_is_hidden_and_listed_in_trash() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+    add_knowledge(&client, &address, &rust, "Systems language").await;
+
+    // Act
+    let deleted = graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": rust, "deletedBy": "alice"}),
+    )
+    .await;
+    let by_name = graphql_request(
+        &client,
+        &address,
+        r#"query { fractal(name: "Rust") { id } }"#,
+        json!({}),
+    )
+    .await;
+    let recreated = create_fractal(&client, &address, "Rust", &root_id, vec![])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    let trash = graphql_request(&client, &address, TRASH, json!({})).await;
+
+    // Assert
+    assert!(deleted.get("errors").is_none(), "{:?}", deleted);
+    assert_eq!(
+        by_name["errors"][0]["extensions"]["code"], "NOT_FOUND",
+        "{:?}",
+        by_name
+    );
+    assert_eq!(
+        recreated["errors"][0]["extensions"]["code"], "INVALID_INPUT",
+        "{:?}",
+        recreated
+    );
+    assert_eq!(
+        child_names(&client, &address, "Programming").await,
+        json!([])
+    );
+
+    let entries = trash["data"]["trash"].as_array().unwrap();
+    assert_eq!(entries.len(), 2, "{:?}", trash);
+    assert!(entries.iter().all(|e| e["deletedBy"] == "alice"));
+    assert!(entries.iter().all(|e| e["deletedAt"].is_string()));
+    assert!(entries
+        .iter()
+        .any(|e| e["node"] == json!({"id": rust, "name": "Rust"})));
+    assert!(entries
+        .iter()
+        .any(|e| e["node"]["content"] == "Systems language"));
+}
+
+#[tokio::test]
+async fn test_restore_fractal_brings_back_the_whole_deletion_with_its_edges() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    let rust = create_named(&client, &address, "Rust", &programming).await;
+    create_named(&client, &address, "Ownership", &rust).await;
+    add_knowledge(&client, &address, &rust, "Systems language").await;
+    graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": programming, "mode": "CASCADE"}),
+    )
+    .await;
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        "mutation ($id: UUID!) { restoreFractal(id: $id) { name } }",
+        json!({"id": programming}),
+    )
+    .await;
+    let knowledge = graphql_request(
+        &client,
+        &address,
+        r#"query { knowledge(fractalName: "Rust", context: []) { content } }"#,
+        json!({}),
+    )
+    .await;
+    let trash = graphql_request(&client, &address, TRASH, json!({})).await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(body["data"]["restoreFractal"]["name"], "Programming");
+    assert_eq!(
+        child_names(&client, &address, "Root").await,
+        json!([{"name": "Programming"}])
+    );
+    assert_eq!(
+        child_names(&client, &address, "Programming").await,
+        json!([{"name": "Rust"}])
+    );
+    assert_eq!(
+        child_names(&client, &address, "Rust").await,
+        json!([{"name": "Ownership"}])
+    );
+    assert_eq!(
+        knowledge["data"]["knowledge"],
+        json!([{"content": "Systems language"}])
+    );
+    assert_eq!(trash["data"]["trash"], json!([]));
+}
+
+#[tokio::test]
+async fn test_restore_fractal_removes_the_links_added_by_reparenting() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let programming = create_named(&client, &address, "Programming", &root_id).await;
+    create_named(&client, &address, "Rust", &programming).await;
+    let python = create_named(&client, &address, "Python", &root_id).await;
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($parentId: UUID!, $childId: UUID!) { addRelation(parentId: $parentId, childId: $childId) }",
+        json!({"parentId": programming, "childId": python}),
+    )
+    .await;
+    graphql_request(
+        &client,
+        &address,
+        DELETE_FRACTAL,
+        json!({"id": programming, "mode": "REPARENT"}),
+    )
+    .await;
+    let sorted_names = |children: serde_json::Value| {
+        let mut names = children
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    let reparented = sorted_names(child_names(&client, &address, "Root").await);
+
+    // Act
+    let body = graphql_request(
+        &client,
+        &address,
+        "mutation ($id: UUID!) { restoreFractal(id: $id) { name } }",
+        json!({"id": programming}),
+    )
+    .await;
+
+    // Assert
+    assert!(body.get("errors").is_none(), "{:?}", body);
+    assert_eq!(reparented, vec!["Python", "Rust"]);
+    // Python was linked to Root before the deletion, so that link stays
+    assert_eq!(
+        sorted_names(child_names(&client, &address, "Root").await),
+        vec!["Programming", "Python"]
+    );
+    assert_eq!(
+        sorted_names(child_names(&client, &address, "Programming").await),
+        vec!["Python", "Rust"]
+    );
+}
+
+#[tokio::test]
+async fn test_restore_knowledge_waits_for_its_fractal() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = Client::new();
+    let root_id = Uuid::nil().to_string();
+    let rust = create_named(&client, &address, "Rust", &root_id).await;
+    let kept = add_knowledge(&client, &address, &rust, "Systems language").await;
+    let other = create_named(&client, &address, "Python", &root_id).await;
+    let orphan = add_knowledge(&client, &address, &other, "Scripting language").await;
+    let restore = "mutation ($id: UUID!) { restoreKnowledge(id: $id) { content } }";
+    graphql_request(
+        &client,
+        &address,
+        "mutation ($id: UUID!) { deleteKnowledge(id: $id, deletedBy: \"bob\") }",
+        json!({"id": kept}),
+    )
+    .await;
+    graphql_request(&client, &address, DELETE_FRACTAL, json!({"id": other})).await;
+
+    // Act
+    let restored = graphql_request(&client, &address, restore, json!({"id": kept})).await;
+    let refused = graphql_request(&client, &address, restore, json!({"id": orphan})).await;
+    let not_trashed = graphql_request(&client, &address, restore, json!({"id": kept})).await;
+
+    // Assert
+    assert_eq!(
+        restored["data"]["restoreKnowledge"]["content"], "Systems language",
+        "{:?}",
+        restored
+    );
+    assert_eq!(
+        refused["errors"][0]["extensions"]["code"], "NOT_FOUND",
+        "{:?}",
+        refused
+    );
+    assert_eq!(
+        not_trashed["errors"][0]["extensions"]["code"], "NOT_FOUND",
+        "{:?}",
+        not_trashed
+    );
+}
+
+#[test]
+fn test_purge_trash_only_removes_items_deleted_before_the_cutoff() {
+    // Arrange
+    let db = empty_db();
+    let conn = data::create_connection(&db).expect("Failed to create connection.");
+    let rust = data::create_fractal(&conn, "Rust", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    data::add_knowledge(&conn, &rust.id, "Systems language", &[]).unwrap();
+    data::delete_fractal(&conn, &rust.id, DeleteMode::Restrict, false, None).unwrap();
+
+    // Act
+    let too_early = data::purge_trash(&conn, Utc::now() - Duration::days(1)).unwrap();
+    let purged = data::purge_trash(&conn, Utc::now() + Duration::days(1)).unwrap();
+    let recreated = data::create_fractal(&conn, "Rust", Some(&FRACTAL_ROOT_ID), None);
+
+    // Assert
+    assert_eq!(too_early, data::PurgeReport::default());
+    assert_eq!(
+        purged,
+        data::PurgeReport {
+            fractals: 1,
+            knowledge: 1
+        }
+    );
+    assert!(recreated.is_ok(), "{:?}", recreated);
+}
+
+#[test]
+fn test_purging_a_context_removes_the_links_and_tags_that_used_it() {
+    // Arrange
+    let db = empty_db();
+    let conn = data::create_connection(&db).expect("Failed to create connection.");
+    let python = data::create_fractal(&conn, "Python", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    let string = data::create_fractal(&conn, "String", Some(&FRACTAL_ROOT_ID), None).unwrap();
+    data::create_fractal(&conn, "Length", Some(&string.id), None).unwrap();
+    data::create_fractal(&conn, ".count()", Some(&string.id), Some(&python.id)).unwrap();
+    data::add_knowledge(&conn, &string.id, "Immutable", &[python.id]).unwrap();
+    data::delete_fractal(&conn, &python.id, DeleteMode::Restrict, false, None).unwrap();
+
+    // Act
+    let report = data::purge_trash(&conn, Utc::now() + Duration::days(1)).unwrap();
+
+    // Assert
+    assert_eq!(report.fractals, 1);
+    let dangling = conn
+        .query(&format!(
+            "MATCH (:Fractal)-[r:HAS_CHILD]->(:Fractal)
+             WHERE r.context_id = UUID('{}')
+             RETURN count(r)",
+            python.id
+        ))
+        .unwrap()
+        .next()
+        .unwrap();
+    assert_eq!(dangling, vec![kuzu::Value::Int64(0)]);
+    let children = data::get_children_of_fractal_with_context(&conn, &string.id, None).unwrap();
+    assert_eq!(
+        children.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+        vec!["Length"]
+    );
+    let tags = conn
+        .query("MATCH (:Knowledge)-[r:IN_CONTEXT]->(:Fractal) RETURN count(r)")
+        .unwrap()
+        .next()
+        .unwrap();
+    assert_eq!(tags, vec![kuzu::Value::Int64(0)]);
+}
@@ -1,9 +1,18 @@
-use kuzu::{Database, SystemConfig};
+use kuzu::{Connection, Database};
 use reqwest::Response;
 use serde_json::json;
-use server::data::{create_fractal_raw, init_database, FRACTAL_ROOT_ID};
+use server::config::Config;
+use server::data::{
+    create_connection, create_db, create_fractal_raw, get_fractal_by_name, init_database,
+    setup_example_graph, FRACTAL_ROOT_ID, IN_MEMORY_DB_PATH,
+};
+use uuid::Uuid;
 
 pub async fn spawn_app() -> String {
+    spawn_app_with_config(Config::default()).await
+}
+
+pub async fn spawn_app_with_config(config: Config) -> String {
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
         .await
         .expect("Failed to bind random port.");
@@ -12,7 +21,7 @@ pub async fn spawn_app() -> String {
         .expect("Failed to get local address.")
         .port();
 
-    let db = Database::new(":memory:", SystemConfig::default()).expect("Failed to create database");
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
     // Create a new scope for database initialization
     {
         let conn = server::data::create_connection(&db).expect("Failed to create connection.");
@@ -21,9 +30,9 @@ pub async fn spawn_app() -> String {
             .expect("Failed to create Root fractal.");
     } // conn is dropped here
 
-    let server = server::run(listener, db).expect("Failed to create a server");
+    let server = server::run(listener, db, &config).expect("Failed to create a server");
 
-    let _ = tokio::spawn(async {
+    tokio::spawn(async {
         server.await.expect("Server failed to start.");
     });
 
@@ -67,7 +76,7 @@ pub async fn create_fractal(
     });
 
     let response = client
-        .post(&format!("{}", address))
+        .post(address)
         .header("Content-Type", "application/json")
         .body(
             json!({
@@ -82,3 +91,75 @@ pub async fn create_fractal(
 
     response
 }
+
+/// Creates a fractal under `parent_id` and returns its id.
+pub async fn create_named(
+    client: &reqwest::Client,
+    address: &str,
+    name: &str,
+    parent_id: &str,
+) -> String {
+    let body = create_fractal(client, address, name, parent_id, vec![])
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+    body["data"]["createFractal"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+pub async fn graphql_request(
+    client: &reqwest::Client,
+    address: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> serde_json::Value {
+    let response = client
+        .post(address)
+        .header("Content-Type", "application/json")
+        .body(
+            json!({
+                "query": query,
+                "variables": variables,
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .expect("Failed to execute GraphQL request.");
+
+    assert!(response.status().is_success());
+
+    response
+        .json::<serde_json::Value>()
+        .await
+        .expect("Failed to parse GraphQL response.")
+}
+
+/// An in-memory database with only Root in it.
+pub fn empty_db() -> Database {
+    let db = create_db(IN_MEMORY_DB_PATH).expect("Failed to create database");
+    {
+        let conn = create_connection(&db).expect("Failed to create connection.");
+        init_database(&conn).expect("Failed to initialize database.");
+        create_fractal_raw(&conn, "Root", None, None, Some(FRACTAL_ROOT_ID))
+            .expect("Failed to create Root fractal.");
+    }
+    db
+}
+
+/// An in-memory database seeded with the example graph.
+pub fn example_db() -> Database {
+    let db = empty_db();
+    {
+        let conn = create_connection(&db).expect("Failed to create connection.");
+        setup_example_graph(&conn).expect("Failed to seed example graph.");
+    }
+    db
+}
+
+pub fn id_of(conn: &Connection, name: &str) -> Uuid {
+    get_fractal_by_name(conn, name).unwrap().id
+}
@@ -14,10 +14,11 @@ import { TypedDocumentNode as DocumentNode } from '@graphql-typed-document-node/
  */
 const documents = {
     "\n  fragment Fractal on FractalGraphQL {\n    id\n    name\n    createdAt\n    updatedAt\n  }\n": types.FractalFragmentDoc,
-    "\n  query Fractal($name: String, $childrenInput: GetFractalChildrenInput!) {\n    fractal(name: $name) {\n      ...Fractal\n      children(input: $childrenInput) {\n        ...Fractal\n      }\n      parents {\n        ...Fractal\n      }\n      contexts {\n        ...Fractal\n      }\n    }\n  }\n": types.FractalDocument,
+    "\n  query Fractal($id: UUID, $childrenInput: GetFractalChildrenInput!) {\n    fractal(id: $id) {\n      ...Fractal\n      children(input: $childrenInput) {\n        ...Fractal\n      }\n      parents {\n        ...Fractal\n      }\n      contexts {\n        ...Fractal\n      }\n    }\n  }\n": types.FractalDocument,
     "\n  mutation CreateFractal($input: CreateFractalInput!) {\n    createFractal(input: $input) {\n      ...Fractal\n    }\n  }\n": types.CreateFractalDocument,
     "\n  mutation AddRelation($parentId: UUID!, $childId: UUID!, $contextId: UUID) {\n    addRelation(parentId: $parentId, childId: $childId, contextId: $contextId)\n  }\n": types.AddRelationDocument,
-    "\n  mutation DeleteFractal($deleteFractalId: UUID!) {\n    deleteFractal(id: $deleteFractalId)\n  }\n": types.DeleteFractalDocument,
+    "\n  mutation DeleteFractal($deleteFractalId: UUID!, $mode: DeleteMode!) {\n    deleteFractal(id: $deleteFractalId, mode: $mode) {\n      deletedFractals {\n        id\n      }\n    }\n  }\n": types.DeleteFractalDocument,
+    "\n  query FractalDescendants($id: UUID) {\n    fractal(id: $id) {\n      id\n      name\n      descendants {\n        fractal {\n          id\n          name\n        }\n        relation {\n          parentId\n          childId\n        }\n      }\n    }\n  }\n": types.FractalDescendantsDocument,
 };
 
 /**
@@ -41,7 +42,7 @@ export function graphql(source: "\n  fragment Fractal on FractalGraphQL {\n    i
 /**
  * The graphql function is used to parse GraphQL queries into a document that can be used by GraphQL clients.
  */
-export function graphql(source: "\n  query Fractal($name: String, $childrenInput: GetFractalChildrenInput!) {\n    fractal(name: $name) {\n      ...Fractal\n      children(input: $childrenInput) {\n        ...Fractal\n      }\n      parents {\n        ...Fractal\n      }\n      contexts {\n        ...Fractal\n      }\n    }\n  }\n"): (typeof documents)["\n  query Fractal($name: String, $childrenInput: GetFractalChildrenInput!) {\n    fractal(name: $name) {\n      ...Fractal\n      children(input: $childrenInput) {\n        ...Fractal\n      }\n      parents {\n        ...Fractal\n      }\n      contexts {\n        ...Fractal\n      }\n    }\n  }\n"];
+export function graphql(source: "\n  query Fractal($id: UUID, $childrenInput: GetFractalChildrenInput!) {\n    fractal(id: $id) {\n      ...Fractal\n      children(input: $childrenInput) {\n        ...Fractal\n      }\n      parents {\n        ...Fractal\n      }\n      contexts {\n        ...Fractal\n      }\n    }\n  }\n"): (typeof documents)["\n  query Fractal($id: UUID, $childrenInput: GetFractalChildrenInput!) {\n    fractal(id: $id) {\n      ...Fractal\n      children(input: $childrenInput) {\n        ...Fractal\n      }\n      parents {\n        ...Fractal\n      }\n      contexts {\n        ...Fractal\n      }\n    }\n  }\n"];
 /**
  * The graphql function is used to parse GraphQL queries into a document that can be used by GraphQL clients.
  */
@@ -53,7 +54,11 @@ export function graphql(source: "\n  mutation AddRelation($parentId: UUID!, $chi
 /**
  * The graphql function is used to parse GraphQL queries into a document that can be used by GraphQL clients.
  */
-export function graphql(source: "\n  mutation DeleteFractal($deleteFractalId: UUID!) {\n    deleteFractal(id: $deleteFractalId)\n  }\n"): (typeof documents)["\n  mutation DeleteFractal($deleteFractalId: UUID!) {\n    deleteFractal(id: $deleteFractalId)\n  }\n"];
+export function graphql(source: "\n  mutation DeleteFractal($deleteFractalId: UUID!, $mode: DeleteMode!) {\n    deleteFractal(id: $deleteFractalId, mode: $mode) {\n      deletedFractals {\n        id\n      }\n    }\n  }\n"): (typeof documents)["\n  mutation DeleteFractal($deleteFractalId: UUID!, $mode: DeleteMode!) {\n    deleteFractal(id: $deleteFractalId, mode: $mode) {\n      deletedFractals {\n        id\n      }\n    }\n  }\n"];
+/**
+ * The graphql function is used to parse GraphQL queries into a document that can be used by GraphQL clients.
+ */
+export function graphql(source: "\n  query FractalDescendants($id: UUID) {\n    fractal(id: $id) {\n      id\n      name\n      descendants {\n        fractal {\n          id\n          name\n        }\n        relation {\n          parentId\n          childId\n        }\n      }\n    }\n  }\n"): (typeof documents)["\n  query FractalDescendants($id: UUID) {\n    fractal(id: $id) {\n      id\n      name\n      descendants {\n        fractal {\n          id\n          name\n        }\n        relation {\n          parentId\n          childId\n        }\n      }\n    }\n  }\n"];
 
 export function graphql(source: string) {
   return (documents as any)[source] ?? {};
@@ -40,44 +40,241 @@ export type AddKnowledgeInput = {
 };
 
 export type CreateFractalInput = {
-  contextIds?: InputMaybe<Scalars['UUID']['input']>;
+  contextIds?: Array<Scalars['UUID']['input']>;
+  /** Fractals the new fractal has as context, see `Fractal.contexts`. */
+  contexts?: Array<Scalars['UUID']['input']>;
   name: Scalars['String']['input'];
-  parentId: Scalars['UUID']['input'];
+  /**
+   * Parent to create the fractal under, once per entry of `contextIds` or without a context
+   * when `contextIds` is empty.
+   */
+  parentId?: InputMaybe<Scalars['UUID']['input']>;
+  /** Further parents, each with its own optional context. */
+  parents?: Array<ParentLinkInput>;
+};
+
+/** What happens to the children of a deleted fractal. */
+export enum DeleteMode {
+  /** Also delete the descendants that have no parent outside of the deleted subtree. */
+  Cascade = 'CASCADE',
+  /** Link the children to every parent of the deleted fractal. */
+  Reparent = 'REPARENT',
+  /** Refuse to delete a fractal that still has children. */
+  Restrict = 'RESTRICT'
+}
+
+export type DeletionReportGraphQl = {
+  __typename?: 'DeletionReportGraphQL';
+  /** Deleted fractals, the requested one first. */
+  deletedFractals: Array<FractalGraphQl>;
+  deletedKnowledge: Array<KnowledgeGraphQl>;
+  dryRun: Scalars['Boolean']['output'];
+  /** Children that were linked to the parents of the deleted fractal. */
+  reparentedFractals: Array<FractalGraphQl>;
+};
+
+export type EdgeGraphQl = {
+  __typename?: 'EdgeGraphQL';
+  contextId?: Maybe<Scalars['UUID']['output']>;
+  relationType: RelationType;
+  sourceId: Scalars['UUID']['output'];
+  targetId: Scalars['UUID']['output'];
 };
 
-export type FractalGraphQl = {
+export type FractalGraphQl = Node & {
   __typename?: 'FractalGraphQL';
+  /** Other names the fractal can be looked up by. */
+  aliases: Array<Scalars['String']['output']>;
+  /** Everything above this fractal up to `maxDepth` levels in one request. */
+  ancestors: Array<HierarchyEntryGraphQl>;
+  /** Knowledge of this fractal ranked by how well it matches `context`, best match first. */
+  bestKnowledge: Array<RankedKnowledgeGraphQl>;
   children: Array<FractalGraphQl>;
   contexts: Array<FractalGraphQl>;
   createdAt: Scalars['DateTime']['output'];
+  /**
+   * Everything below this fractal up to `maxDepth` levels in one request, following only
+   * links in exactly `contextId` when given. A fractal with several parents is listed once
+   * per link.
+   */
+  descendants: Array<HierarchyEntryGraphQl>;
   id: Scalars['UUID']['output'];
+  /**
+   * Knowledge of this fractal, oldest first. When `context` is given only knowledge tagged
+   * with every one of those contexts is returned.
+   */
+  knowledges: KnowledgeGraphQlConnection;
+  /**
+   * The alias the fractal was looked up by in `fractal(name:)`, `null` when it was its name or
+   * it was not looked up by name.
+   */
+  matchedAlias?: Maybe<Scalars['String']['output']>;
   name: Scalars['String']['output'];
   parents: Array<FractalGraphQl>;
+  /**
+   * Every way down from Root to this fractal with the context of each link, e.g. for
+   * breadcrumbs. Links are only followed in a context that is Root or comes earlier on the
+   * path, like in `fractalByPath`. With `contextId` the paths that apply in that context are
+   * listed, following links in exactly `contextId` as well. With `throughId` only the paths
+   * through that fractal are listed.
+   */
+  pathsFromRoot: Array<PathGraphQl>;
+  /** Fractals that have this fractal as one of their `contexts`. */
+  providesContextFor: Array<FractalGraphQl>;
   updatedAt: Scalars['DateTime']['output'];
 };
 
 
+export type FractalGraphQlAncestorsArgs = {
+  maxDepth?: InputMaybe<Scalars['Int']['input']>;
+};
+
+
+export type FractalGraphQlBestKnowledgeArgs = {
+  context: Array<Scalars['UUID']['input']>;
+  first?: InputMaybe<Scalars['Int']['input']>;
+};
+
+
 export type FractalGraphQlChildrenArgs = {
-  input: GetFractalChildrenInput;
+  input?: InputMaybe<GetFractalChildrenInput>;
+};
+
+
+export type FractalGraphQlDescendantsArgs = {
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  maxDepth?: InputMaybe<Scalars['Int']['input']>;
+};
+
+
+export type FractalGraphQlKnowledgesArgs = {
+  after?: InputMaybe<Scalars['String']['input']>;
+  context?: InputMaybe<Array<Scalars['UUID']['input']>>;
+  first?: InputMaybe<Scalars['Int']['input']>;
+};
+
+
+export type FractalGraphQlPathsFromRootArgs = {
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  throughId?: InputMaybe<Scalars['UUID']['input']>;
 };
 
 export type GetFractalChildrenInput = {
   contextId?: InputMaybe<Scalars['UUID']['input']>;
 };
 
-export type KnowledgeGraphQl = {
+export type HierarchyEntryGraphQl = {
+  __typename?: 'HierarchyEntryGraphQL';
+  /** Levels away from the fractal the walk started at, `1` for direct children or parents. */
+  depth: Scalars['Int']['output'];
+  fractal: FractalGraphQl;
+  relation: RelationGraphQl;
+};
+
+export type KnowledgeGraphQl = Node & {
   __typename?: 'KnowledgeGraphQL';
   content: Scalars['String']['output'];
+  /** The fractals this knowledge applies in. */
+  contexts: Array<FractalGraphQl>;
+  createdAt: Scalars['DateTime']['output'];
+  /** The fractal this knowledge belongs to. */
   fractal: FractalGraphQl;
   id: Scalars['UUID']['output'];
+  updatedAt: Scalars['DateTime']['output'];
+};
+
+export type KnowledgeGraphQlConnection = {
+  __typename?: 'KnowledgeGraphQLConnection';
+  /** A list of edges. */
+  edges: Array<KnowledgeGraphQlEdge>;
+  /** A list of nodes. */
+  nodes: Array<KnowledgeGraphQl>;
+  /** Information to aid in pagination. */
+  pageInfo: PageInfo;
+};
+
+/** An edge in a connection. */
+export type KnowledgeGraphQlEdge = {
+  __typename?: 'KnowledgeGraphQLEdge';
+  /** A cursor for use in pagination */
+  cursor: Scalars['String']['output'];
+  /** The item at the end of the edge */
+  node: KnowledgeGraphQl;
 };
 
 export type MutationRoot = {
   __typename?: 'MutationRoot';
+  /**
+   * Lets the fractal also be looked up as `alias`. Fails when a fractal next to it is already
+   * called `alias` or has it as an alias.
+   */
+  addAlias: FractalGraphQl;
+  /**
+   * Gives `fractalId` the context `contextId`. Returns whether it was added, `false` when the
+   * fractal already had that context.
+   */
+  addContext: Scalars['Boolean']['output'];
   addKnowledge: KnowledgeGraphQl;
+  /**
+   * Links `childId` under `parentId` in `contextId`. Returns whether a new link was created,
+   * `false` when the same link already existed.
+   */
   addRelation: Scalars['Boolean']['output'];
+  /**
+   * Copies `id` with all of its descendants and their knowledge under `toParentId` in
+   * `contextId`. Copies are named after `renames`, or get `suffix` appended to their name.
+   * Returns the copies, the copy of `id` first.
+   */
+  copySubtree: Array<FractalGraphQl>;
   createFractal: FractalGraphQl;
-  deleteFractal: Scalars['Boolean']['output'];
+  /**
+   * Moves a fractal and its knowledge to the trash. `mode` decides what happens to its
+   * children, with `dryRun` nothing is deleted and the result lists what would be.
+   * `deletedBy` is shown in the trash.
+   */
+  deleteFractal: DeletionReportGraphQl;
+  /** Moves a knowledge entry to the trash. `deletedBy` is shown in the trash. */
+  deleteKnowledge: Scalars['Boolean']['output'];
+  /**
+   * Moves `id` from under `fromParentId` to under `toParentId`. `contextId` picks the link to
+   * move, it keeps its context.
+   */
+  moveFractal: FractalGraphQl;
+  /** Moves a knowledge entry to another fractal. */
+  moveKnowledge: KnowledgeGraphQl;
+  removeAlias: FractalGraphQl;
+  /** Removes the context `contextId` from `fractalId`. Returns whether the fractal had it. */
+  removeContext: Scalars['Boolean']['output'];
+  /**
+   * Removes the link from `parentId` to `childId` in `contextId`, or the link without a
+   * context when `contextId` is omitted. Returns whether a link was removed.
+   */
+  removeRelation: Scalars['Boolean']['output'];
+  /** Takes a fractal out of the trash together with everything that was deleted with it. */
+  restoreFractal: FractalGraphQl;
+  /** Takes a knowledge entry out of the trash. Fails while its fractal is still in the trash. */
+  restoreKnowledge: KnowledgeGraphQl;
+  /** Replaces the contexts of a knowledge entry. */
+  setKnowledgeContexts: KnowledgeGraphQl;
+  updateFractal: FractalGraphQl;
+  updateKnowledge: KnowledgeGraphQl;
+  /**
+   * Moves the link from `parentId` to `childId` in `contextId` to `newContextId`. Omitted
+   * contexts stand for the link without a context. Returns whether a link was updated.
+   */
+  updateRelationContext: Scalars['Boolean']['output'];
+};
+
+
+export type MutationRootAddAliasArgs = {
+  alias: Scalars['String']['input'];
+  id: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootAddContextArgs = {
+  contextId: Scalars['UUID']['input'];
+  fractalId: Scalars['UUID']['input'];
 };
 
 
@@ -93,36 +290,312 @@ export type MutationRootAddRelationArgs = {
 };
 
 
+export type MutationRootCopySubtreeArgs = {
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  id: Scalars['UUID']['input'];
+  renames?: Array<RenameInput>;
+  suffix?: Scalars['String']['input'];
+  toParentId: Scalars['UUID']['input'];
+};
+
+
 export type MutationRootCreateFractalArgs = {
   input: CreateFractalInput;
 };
 
 
 export type MutationRootDeleteFractalArgs = {
+  deletedBy?: InputMaybe<Scalars['String']['input']>;
+  dryRun?: Scalars['Boolean']['input'];
+  id: Scalars['UUID']['input'];
+  mode?: DeleteMode;
+};
+
+
+export type MutationRootDeleteKnowledgeArgs = {
+  deletedBy?: InputMaybe<Scalars['String']['input']>;
+  id: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootMoveFractalArgs = {
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  fromParentId: Scalars['UUID']['input'];
+  id: Scalars['UUID']['input'];
+  toParentId: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootMoveKnowledgeArgs = {
+  fractalId: Scalars['UUID']['input'];
+  id: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootRemoveAliasArgs = {
+  alias: Scalars['String']['input'];
+  id: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootRemoveContextArgs = {
+  contextId: Scalars['UUID']['input'];
+  fractalId: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootRemoveRelationArgs = {
+  childId: Scalars['UUID']['input'];
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  parentId: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootRestoreFractalArgs = {
   id: Scalars['UUID']['input'];
 };
 
+
+export type MutationRootRestoreKnowledgeArgs = {
+  id: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootSetKnowledgeContextsArgs = {
+  contextIds: Array<Scalars['UUID']['input']>;
+  id: Scalars['UUID']['input'];
+};
+
+
+export type MutationRootUpdateFractalArgs = {
+  input: UpdateFractalInput;
+};
+
+
+export type MutationRootUpdateKnowledgeArgs = {
+  input: UpdateKnowledgeInput;
+};
+
+
+export type MutationRootUpdateRelationContextArgs = {
+  childId: Scalars['UUID']['input'];
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  newContextId?: InputMaybe<Scalars['UUID']['input']>;
+  parentId: Scalars['UUID']['input'];
+};
+
+export type NameCandidateGraphQl = {
+  __typename?: 'NameCandidateGraphQL';
+  fractal: FractalGraphQl;
+  /** The alias that matched, `null` when the fractal is called by the name itself. */
+  matchedAlias?: Maybe<Scalars['String']['output']>;
+  /** Every way down from Root to the fractal, see `Fractal.pathsFromRoot`. */
+  paths: Array<PathGraphQl>;
+};
+
+/** An object with a globally unique id that can be fetched with `node(id:)`. */
+export type Node = {
+  id: Scalars['UUID']['output'];
+};
+
+/** Information about pagination in a connection */
+export type PageInfo = {
+  __typename?: 'PageInfo';
+  /** When paginating forwards, the cursor to continue. */
+  endCursor?: Maybe<Scalars['String']['output']>;
+  /** When paginating forwards, are there more items? */
+  hasNextPage: Scalars['Boolean']['output'];
+  /** When paginating backwards, are there more items? */
+  hasPreviousPage: Scalars['Boolean']['output'];
+  /** When paginating backwards, the cursor to continue. */
+  startCursor?: Maybe<Scalars['String']['output']>;
+};
+
+export type ParentLinkInput = {
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  parentId: Scalars['UUID']['input'];
+};
+
+export type PathGraphQl = {
+  __typename?: 'PathGraphQL';
+  /** The edge between each fractal and the next, with its stored direction. */
+  edges: Array<EdgeGraphQl>;
+  /** Every fractal on the path, in order. */
+  fractals: Array<FractalGraphQl>;
+  /** Number of edges. */
+  length: Scalars['Int']['output'];
+};
+
 export type QueryRoot = {
   __typename?: 'QueryRoot';
+  /**
+   * Knowledge of the named fractal ranked by how well it matches `context`, best match
+   * first. Knowledge tagged with an ancestor of a requested context counts partially.
+   */
+  bestKnowledge: Array<RankedKnowledgeGraphQl>;
+  /**
+   * Looks a fractal up by `id` or by `name`, defaulting to Root when neither is given. Names
+   * are only unique per parent and context, so a `name` shared by several fractals fails with
+   * `AMBIGUOUS_NAME`; `fractalsByName` and `fractalByPath` tell them apart.
+   */
   fractal: FractalGraphQl;
-  knowledge: KnowledgeGraphQl;
+  /**
+   * Looks a fractal up by the names on the way down from Root, e.g. `Programming/Rust/String`.
+   * Links in a context are only followed when that context is part of the path.
+   */
+  fractalByPath: FractalGraphQl;
+  /**
+   * Looks several fractals up by id. Ids that do not exist resolve to `null`, positions match
+   * the requested `ids`.
+   */
+  fractals: Array<Maybe<FractalGraphQl>>;
+  /**
+   * Every fractal called `name` or having it as an alias, with the paths leading to it from
+   * Root, since names are only unique among the children a parent has in one context.
+   */
+  fractalsByName: Array<NameCandidateGraphQl>;
+  /**
+   * All knowledge of the named fractal that is tagged with every one of `context`, oldest
+   * first.
+   */
+  knowledge: Array<KnowledgeGraphQl>;
+  /** Fetches any object by its globally unique id. */
+  node?: Maybe<Node>;
+  /**
+   * Up to `k` shortest ways to get from `fromId` to `toId`, following `HAS_CHILD` and
+   * `HAS_CONTEXT` edges in either direction. `relationTypes` narrows that down, `HAS_KNOWLEDGE`
+   * is not allowed.
+   */
+  paths: Array<PathGraphQl>;
+  /**
+   * Everything reachable from `rootId` in at most `depth` steps as flat, deduplicated lists
+   * of nodes and edges. Only edges of `relationTypes` are followed, all of them when omitted,
+   * and only `HAS_CHILD` edges in exactly `contextId` when it is given. At most `maxNodes`
+   * nodes are returned, nearest first, and `truncated` tells whether any were left out.
+   */
+  subgraph: SubgraphGraphQl;
+  /** Deleted fractals and knowledge, most recently deleted first. */
+  trash: Array<TrashEntryGraphQl>;
+};
+
+
+export type QueryRootBestKnowledgeArgs = {
+  context: Array<Scalars['UUID']['input']>;
+  first?: InputMaybe<Scalars['Int']['input']>;
+  fractalName: Scalars['String']['input'];
 };
 
 
 export type QueryRootFractalArgs = {
+  id?: InputMaybe<Scalars['UUID']['input']>;
   name?: InputMaybe<Scalars['String']['input']>;
 };
 
 
+export type QueryRootFractalByPathArgs = {
+  path: Scalars['String']['input'];
+};
+
+
+export type QueryRootFractalsArgs = {
+  ids: Array<Scalars['UUID']['input']>;
+};
+
+
+export type QueryRootFractalsByNameArgs = {
+  name: Scalars['String']['input'];
+};
+
+
 export type QueryRootKnowledgeArgs = {
   context: Array<Scalars['UUID']['input']>;
   fractalName: Scalars['String']['input'];
 };
 
+
+export type QueryRootNodeArgs = {
+  id: Scalars['UUID']['input'];
+};
+
+
+export type QueryRootPathsArgs = {
+  fromId: Scalars['UUID']['input'];
+  k?: Scalars['Int']['input'];
+  maxLength?: InputMaybe<Scalars['Int']['input']>;
+  relationTypes?: InputMaybe<Array<RelationType>>;
+  toId: Scalars['UUID']['input'];
+};
+
+
+export type QueryRootSubgraphArgs = {
+  contextId?: InputMaybe<Scalars['UUID']['input']>;
+  depth: Scalars['Int']['input'];
+  maxNodes?: InputMaybe<Scalars['Int']['input']>;
+  relationTypes?: InputMaybe<Array<RelationType>>;
+  rootId: Scalars['UUID']['input'];
+};
+
+export type RankedKnowledgeGraphQl = {
+  __typename?: 'RankedKnowledgeGraphQL';
+  knowledge: KnowledgeGraphQl;
+  /** Requested contexts that were matched, exactly or through one of their ancestors. */
+  matchedContextIds: Array<Scalars['UUID']['output']>;
+  /** Share of the requested contexts matched, from 0 to 1. */
+  score: Scalars['Float']['output'];
+};
+
+export type RelationGraphQl = {
+  __typename?: 'RelationGraphQL';
+  childId: Scalars['UUID']['output'];
+  contextId?: Maybe<Scalars['UUID']['output']>;
+  parentId: Scalars['UUID']['output'];
+};
+
+/** Kinds of edges followed by `subgraph` and `paths`. */
+export enum RelationType {
+  HasChild = 'HAS_CHILD',
+  HasContext = 'HAS_CONTEXT',
+  HasKnowledge = 'HAS_KNOWLEDGE'
+}
+
+/** Gives the copy of the fractal `id` the name `to`. */
+export type RenameInput = {
+  id: Scalars['UUID']['input'];
+  to: Scalars['String']['input'];
+};
+
+export type SubgraphGraphQl = {
+  __typename?: 'SubgraphGraphQL';
+  edges: Array<EdgeGraphQl>;
+  /** The root first, then the other fractals, then knowledge. */
+  nodes: Array<Node>;
+  /** Whether nodes within reach were left out because of `maxNodes`. */
+  truncated: Scalars['Boolean']['output'];
+};
+
+export type TrashEntryGraphQl = {
+  __typename?: 'TrashEntryGraphQL';
+  deletedAt: Scalars['DateTime']['output'];
+  deletedBy?: Maybe<Scalars['String']['output']>;
+  node: Node;
+};
+
+export type UpdateFractalInput = {
+  /** When set, the update is rejected unless the fractal's `updatedAt` still has this value. */
+  expectedUpdatedAt?: InputMaybe<Scalars['DateTime']['input']>;
+  id: Scalars['UUID']['input'];
+  name?: InputMaybe<Scalars['String']['input']>;
+};
+
+export type UpdateKnowledgeInput = {
+  content: Scalars['String']['input'];
+  id: Scalars['UUID']['input'];
+};
+
 export type FractalFragment = { __typename?: 'FractalGraphQL', id: any, name: string, createdAt: any, updatedAt: any } & { ' $fragmentName'?: 'FractalFragment' };
 
 export type FractalQueryVariables = Exact<{
-  name?: InputMaybe<Scalars['String']['input']>;
+  id?: InputMaybe<Scalars['UUID']['input']>;
   childrenInput: GetFractalChildrenInput;
 }>;
 
@@ -162,13 +635,22 @@ export type AddRelationMutation = { __typename?: 'MutationRoot', addRelation: bo
 
 export type DeleteFractalMutationVariables = Exact<{
   deleteFractalId: Scalars['UUID']['input'];
+  mode: DeleteMode;
+}>;
+
+
+export type DeleteFractalMutation = { __typename?: 'MutationRoot', deleteFractal: { __typename?: 'DeletionReportGraphQL', deletedFractals: Array<{ __typename?: 'FractalGraphQL', id: any }> } };
+
+export type FractalDescendantsQueryVariables = Exact<{
+  id?: InputMaybe<Scalars['UUID']['input']>;
 }>;
 
 
-export type DeleteFractalMutation = { __typename?: 'MutationRoot', deleteFractal: boolean };
+export type FractalDescendantsQuery = { __typename?: 'QueryRoot', fractal: { __typename?: 'FractalGraphQL', id: any, name: string, descendants: Array<{ __typename?: 'HierarchyEntryGraphQL', fractal: { __typename?: 'FractalGraphQL', id: any, name: string }, relation: { __typename?: 'RelationGraphQL', parentId: any, childId: any } }> } };
 
 export const FractalFragmentDoc = {"kind":"Document","definitions":[{"kind":"FragmentDefinition","name":{"kind":"Name","value":"Fractal"},"typeCondition":{"kind":"NamedType","name":{"kind":"Name","value":"FractalGraphQL"}},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}},{"kind":"Field","name":{"kind":"Name","value":"name"}},{"kind":"Field","name":{"kind":"Name","value":"createdAt"}},{"kind":"Field","name":{"kind":"Name","value":"updatedAt"}}]}}]} as unknown as DocumentNode<FractalFragment, unknown>;
-export const FractalDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"query","name":{"kind":"Name","value":"Fractal"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"name"}},"type":{"kind":"NamedType","name":{"kind":"Name","value":"String"}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"childrenInput"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"GetFractalChildrenInput"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"fractal"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"name"},"value":{"kind":"Variable","name":{"kind":"Name","value":"name"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}},{"kind":"Field","name":{"kind":"Name","value":"children"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"input"},"value":{"kind":"Variable","name":{"kind":"Name","value":"childrenInput"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}},{"kind":"Field","name":{"kind":"Name","value":"parents"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}},{"kind":"Field","name":{"kind":"Name","value":"contexts"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}}]}}]}},{"kind":"FragmentDefinition","name":{"kind":"Name","value":"Fractal"},"typeCondition":{"kind":"NamedType","name":{"kind":"Name","value":"FractalGraphQL"}},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}},{"kind":"Field","name":{"kind":"Name","value":"name"}},{"kind":"Field","name":{"kind":"Name","value":"createdAt"}},{"kind":"Field","name":{"kind":"Name","value":"updatedAt"}}]}}]} as unknown as DocumentNode<FractalQuery, FractalQueryVariables>;
+export const FractalDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"query","name":{"kind":"Name","value":"Fractal"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"id"}},"type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"childrenInput"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"GetFractalChildrenInput"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"fractal"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"id"},"value":{"kind":"Variable","name":{"kind":"Name","value":"id"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}},{"kind":"Field","name":{"kind":"Name","value":"children"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"input"},"value":{"kind":"Variable","name":{"kind":"Name","value":"childrenInput"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}},{"kind":"Field","name":{"kind":"Name","value":"parents"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}},{"kind":"Field","name":{"kind":"Name","value":"contexts"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}}]}}]}},{"kind":"FragmentDefinition","name":{"kind":"Name","value":"Fractal"},"typeCondition":{"kind":"NamedType","name":{"kind":"Name","value":"FractalGraphQL"}},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}},{"kind":"Field","name":{"kind":"Name","value":"name"}},{"kind":"Field","name":{"kind":"Name","value":"createdAt"}},{"kind":"Field","name":{"kind":"Name","value":"updatedAt"}}]}}]} as unknown as DocumentNode<FractalQuery, FractalQueryVariables>;
 export const CreateFractalDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"mutation","name":{"kind":"Name","value":"CreateFractal"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"input"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"CreateFractalInput"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"createFractal"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"input"},"value":{"kind":"Variable","name":{"kind":"Name","value":"input"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"FragmentSpread","name":{"kind":"Name","value":"Fractal"}}]}}]}},{"kind":"FragmentDefinition","name":{"kind":"Name","value":"Fractal"},"typeCondition":{"kind":"NamedType","name":{"kind":"Name","value":"FractalGraphQL"}},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}},{"kind":"Field","name":{"kind":"Name","value":"name"}},{"kind":"Field","name":{"kind":"Name","value":"createdAt"}},{"kind":"Field","name":{"kind":"Name","value":"updatedAt"}}]}}]} as unknown as DocumentNode<CreateFractalMutation, CreateFractalMutationVariables>;
 export const AddRelationDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"mutation","name":{"kind":"Name","value":"AddRelation"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"parentId"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"childId"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"contextId"}},"type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"addRelation"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"parentId"},"value":{"kind":"Variable","name":{"kind":"Name","value":"parentId"}}},{"kind":"Argument","name":{"kind":"Name","value":"childId"},"value":{"kind":"Variable","name":{"kind":"Name","value":"childId"}}},{"kind":"Argument","name":{"kind":"Name","value":"contextId"},"value":{"kind":"Variable","name":{"kind":"Name","value":"contextId"}}}]}]}}]} as unknown as DocumentNode<AddRelationMutation, AddRelationMutationVariables>;
-export const DeleteFractalDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"mutation","name":{"kind":"Name","value":"DeleteFractal"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"deleteFractalId"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"deleteFractal"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"id"},"value":{"kind":"Variable","name":{"kind":"Name","value":"deleteFractalId"}}}]}]}}]} as unknown as DocumentNode<DeleteFractalMutation, DeleteFractalMutationVariables>;
\ No newline at end of file
+export const DeleteFractalDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"mutation","name":{"kind":"Name","value":"DeleteFractal"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"deleteFractalId"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"mode"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"DeleteMode"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"deleteFractal"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"id"},"value":{"kind":"Variable","name":{"kind":"Name","value":"deleteFractalId"}}},{"kind":"Argument","name":{"kind":"Name","value":"mode"},"value":{"kind":"Variable","name":{"kind":"Name","value":"mode"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"deletedFractals"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}}]}}]}}]}}]} as unknown as DocumentNode<DeleteFractalMutation, DeleteFractalMutationVariables>;
+export const FractalDescendantsDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"query","name":{"kind":"Name","value":"FractalDescendants"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"id"}},"type":{"kind":"NamedType","name":{"kind":"Name","value":"UUID"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"fractal"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"id"},"value":{"kind":"Variable","name":{"kind":"Name","value":"id"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}},{"kind":"Field","name":{"kind":"Name","value":"name"}},{"kind":"Field","name":{"kind":"Name","value":"descendants"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"fractal"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"}},{"kind":"Field","name":{"kind":"Name","value":"name"}}]}},{"kind":"Field","name":{"kind":"Name","value":"relation"},"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"parentId"}},{"kind":"Field","name":{"kind":"Name","value":"childId"}}]}}]}}]}}]}}]} as unknown as DocumentNode<FractalDescendantsQuery, FractalDescendantsQueryVariables>;
\ No newline at end of file
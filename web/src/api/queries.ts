@@ -10,8 +10,8 @@ export const Fractal = graphql(/* GraphQL */ `
 `);
 
 export const FRACTAL = graphql(/* GraphQL */ `
-  query Fractal($name: String, $childrenInput: GetFractalChildrenInput!) {
-    fractal(name: $name) {
+  query Fractal($id: UUID, $childrenInput: GetFractalChildrenInput!) {
+    fractal(id: $id) {
       ...Fractal
       children(input: $childrenInput) {
         ...Fractal
@@ -41,7 +41,30 @@ export const ADD_RELATION = graphql(/* GraphQL */ `
 `);
 
 export const DELETE_FRACTAL = graphql(/* GraphQL */ `
-  mutation DeleteFractal($deleteFractalId: UUID!) {
-    deleteFractal(id: $deleteFractalId)
+  mutation DeleteFractal($deleteFractalId: UUID!, $mode: DeleteMode!) {
+    deleteFractal(id: $deleteFractalId, mode: $mode) {
+      deletedFractals {
+        id
+      }
+    }
+  }
+`);
+
+export const FRACTAL_DESCENDANTS = graphql(/* GraphQL */ `
+  query FractalDescendants($id: UUID) {
+    fractal(id: $id) {
+      id
+      name
+      descendants {
+        fractal {
+          id
+          name
+        }
+        relation {
+          parentId
+          childId
+        }
+      }
+    }
   }
 `);
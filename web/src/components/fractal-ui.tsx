@@ -16,6 +16,7 @@ import {
   ADD_RELATION,
   CREATE_FRACTAL,
   DELETE_FRACTAL,
+  DeleteMode,
   FRACTAL,
   FractalGraphQl,
 } from "@/api";
@@ -44,12 +45,17 @@ import {
 import { Input } from "@/components/ui/input";
 import { Textarea } from "@/components/ui/textarea";
 import { useToast } from "@/hooks";
-import { useLazyQuery, useMutation, useQuery } from "@apollo/client";
+import {
+  ApolloError,
+  useLazyQuery,
+  useMutation,
+  useQuery,
+} from "@apollo/client";
 import { DeepPartial } from "@apollo/client/utilities";
 
 export const FractalUi: React.FC = () => {
   const { data, loading, error } = useQuery(FRACTAL, {
-    variables: { name: "Root", childrenInput: { contextId: null } },
+    variables: { childrenInput: { contextId: null } },
   });
 
   if (loading) {
@@ -91,22 +97,25 @@ const FractalNode: React.FC<{
 
   const [getFractal, { data, loading }] = useLazyQuery(FRACTAL);
   const [createFractal] = useMutation(CREATE_FRACTAL, {
-    refetchQueries: [{ query: FRACTAL, variables: { name: fractal.name } }],
+    refetchQueries: [{ query: FRACTAL, variables: { id: fractal.id } }],
   });
   const [deleteFractal] = useMutation(DELETE_FRACTAL, {
-    refetchQueries: parent?.name
-      ? [{ query: FRACTAL, variables: { name: parent.name } }]
+    refetchQueries: parent?.id
+      ? [{ query: FRACTAL, variables: { id: parent.id } }]
       : [],
   });
   const [addRelation] = useMutation(ADD_RELATION, {
-    refetchQueries: [{ query: FRACTAL, variables: { name: fractal.name } }],
+    refetchQueries: [{ query: FRACTAL, variables: { id: fractal.id } }],
   });
 
   const handleDelete = async () => {
     if (fractal.id) {
       try {
         await deleteFractal({
-          variables: { deleteFractalId: fractal.id },
+          variables: {
+            deleteFractalId: fractal.id,
+            mode: DeleteMode.Restrict,
+          },
         });
         toast({
           title: "Deleted",
@@ -114,9 +123,14 @@ const FractalNode: React.FC<{
         });
       } catch (error) {
         console.error("Failed to delete fractal:", error);
+        const hasChildren =
+          error instanceof ApolloError &&
+          error.graphQLErrors.some((e) => e.extensions?.code === "CONFLICT");
         toast({
           title: "Error",
-          description: "Failed to delete fractal.",
+          description: hasChildren
+            ? "Fractal still has children. Delete or move them first."
+            : "Failed to delete fractal.",
           variant: "destructive",
         });
       }
@@ -247,10 +261,11 @@ const FractalNode: React.FC<{
       </AlertDialogTrigger>
       <AlertDialogContent>
         <AlertDialogHeader>
-          <AlertDialogTitle>Are you absolutely sure?</AlertDialogTitle>
+          <AlertDialogTitle>Move this fractal to the trash?</AlertDialogTitle>
           <AlertDialogDescription>
-            This action cannot be undone. This will permanently delete the
-            fractal and all its associated data.
+            The fractal and its knowledge move to the trash, where they can be
+            restored until the trash is purged. Fractals that still have
+            children cannot be deleted.
           </AlertDialogDescription>
         </AlertDialogHeader>
         <AlertDialogFooter>
@@ -271,7 +286,7 @@ const FractalNode: React.FC<{
             if (!fractal.children) {
               await getFractal({
                 variables: {
-                  name: fractal.name,
+                  id: fractal.id,
                   childrenInput: { contextId: parent?.id },
                 },
               });
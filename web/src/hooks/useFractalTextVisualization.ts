@@ -1,100 +1,72 @@
-import { FRACTAL, FractalGraphQl } from "@/api";
+import { FRACTAL_DESCENDANTS, FractalGraphQl } from "@/api";
 import { useLazyQuery } from "@apollo/client";
 import { DeepPartial } from "@apollo/client/utilities";
 import { useState } from "react";
 
+type Node = { id: string; name: string };
+
 export const useFractalVisualization = () => {
   const [visualization, setVisualization] = useState<string>("");
-  const [getFractal] = useLazyQuery(FRACTAL);
+  const [getDescendants] = useLazyQuery(FRACTAL_DESCENDANTS);
 
   const traverseAndVisualizeGraph = async (
     rootFractal: DeepPartial<FractalGraphQl>
   ) => {
-    const visited = new Set<string>();
+    // The whole subtree is loaded in one request and walked in memory
+    const { data } = await getDescendants({
+      variables: { id: rootFractal.id },
+    });
+    if (!data || !data.fractal) return;
+
+    const root: Node = { id: data.fractal.id, name: data.fractal.name };
+    const nodes = new Map<string, Node>([[root.id, root]]);
+    const children = new Map<string, string[]>();
+    const parents = new Map<string, string[]>();
+
+    for (const { fractal, relation } of data.fractal.descendants) {
+      nodes.set(fractal.id, { id: fractal.id, name: fractal.name });
+      children.set(relation.parentId, [
+        ...(children.get(relation.parentId) ?? []),
+        relation.childId,
+      ]);
+      parents.set(relation.childId, [
+        ...(parents.get(relation.childId) ?? []),
+        relation.parentId,
+      ]);
+    }
+
     let visualizationText = "";
 
-    const dfs = async (
-      fractal: DeepPartial<FractalGraphQl>,
+    const dfs = (
+      id: string,
       depth: number,
       direction: "down" | "up",
       path: string[] = []
     ) => {
-      if (
-        !fractal.id ||
-        visited.has(`${direction}-${fractal.id}-${path.join(",")}`)
-      )
-        return;
-      visited.add(`${direction}-${fractal.id}-${path.join(",")}`);
+      const node = nodes.get(id);
+      // Links in different contexts can form a loop
+      if (!node || path.includes(id)) return;
 
       const indent = "  ".repeat(depth);
       const prefix = direction === "down" ? "↓ " : "↑ ";
-      visualizationText += `${indent}${prefix}${fractal.name} (ID: ${fractal.id})\n`;
-
-      if (direction === "down") {
-        if (!fractal.children || fractal.children.length === 0) {
-          // Fetch children if not available
-          const { data } = await getFractal({
-            variables: { name: fractal.name },
-          });
-          if (data && data.fractal) {
-            fractal = data.fractal;
-          }
-        }
-        if (fractal.children && fractal.children.length > 0) {
-          for (const child of fractal.children) {
-            if (child) {
-              await dfs(child, depth + 1, "down", [...path, fractal.id]);
-            }
-          }
-        }
-      }
+      visualizationText += `${indent}${prefix}${node.name} (ID: ${node.id})\n`;
 
-      if (direction === "up") {
-        if (!fractal.parents || fractal.parents.length === 0) {
-          // Fetch parents if not available
-          const { data } = await getFractal({
-            variables: { name: fractal.name },
-          });
-          if (data && data.fractal) {
-            fractal = data.fractal;
-          }
-        }
-        if (fractal.parents && fractal.parents.length > 0) {
-          for (const parent of fractal.parents) {
-            if (parent) {
-              await dfs(parent, depth + 1, "up", [...path, fractal.id]);
-            }
-          }
-        }
+      const next = (direction === "down" ? children : parents).get(id) ?? [];
+      for (const nextId of next) {
+        dfs(nextId, depth + 1, direction, [...path, id]);
       }
     };
 
     // Traverse down from root to leaves
     visualizationText += "Traversal from root to leaves:\n";
-    await dfs(rootFractal, 0, "down");
+    dfs(root.id, 0, "down");
 
-    // Reset visited set for upward traversal
-    visited.clear();
+    // Traverse up from each leaf to all possible roots
     visualizationText += "\nTraversal from leaves to root:\n";
-
-    // Find all leaf nodes
-    const leafNodes: DeepPartial<FractalGraphQl>[] = [];
-    const findLeafNodes = (fractal: DeepPartial<FractalGraphQl>) => {
-      if (!fractal.children || fractal.children.length === 0) {
-        leafNodes.push(fractal);
-      } else if (fractal.children && fractal.children.length > 0) {
-        for (const child of fractal.children) {
-          if (child) {
-            findLeafNodes(child);
-          }
-        }
+    for (const node of nodes.values()) {
+      if (!children.has(node.id)) {
+        dfs(node.id, 0, "up");
       }
-    };
-    findLeafNodes(rootFractal);
-
-    // Traverse up from each leaf to all possible roots
-    for (const leaf of leafNodes) {
-      await dfs(leaf, 0, "up");
     }
 
     setVisualization(visualizationText);